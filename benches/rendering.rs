@@ -0,0 +1,390 @@
+//! Benchmarks of the parts of a frame that don't need a window: laying
+//! out the grid, looking up the positions of the cells, rendering their
+//! colors, drawing them up to the draw calls, and converting patterns
+//! and screen points.
+//!
+//! They go through the same internal helpers as the frontends, which
+//! the `bench` feature exposes:
+//!
+//! ```sh
+//! cargo bench --features bench
+//! ```
+//!
+//! Drawing itself needs a window, so the draw loops push the rectangles
+//! into a list instead of drawing them.  Only the `drawing` group opens a
+//! (hidden) window, to compare drawing the cells one by one with drawing
+//! them in batches.
+
+use std::{collections::HashMap, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lifers_raylib::{
+    bench::{
+        cell_coordinates, covers_window, fit_camera, lay_out, DenseColors, GridSnapshot,
+        LayoutInputs, RectBatch,
+    },
+    overlay::OverlayContext,
+    patterns::{self, Pattern},
+};
+use raylib::{
+    color::Color,
+    drawing::{RaylibDraw, RaylibTextureModeExt},
+    math::{Rectangle, Vector2},
+};
+
+/// The sides of the (square) grids benchmarked.
+const GRID_SIZES: [usize; 3] = [64, 256, 1024];
+/// The side of the (square) window the grids are laid out in.
+const WINDOW_SIZE: f32 = 1024.;
+const CELL_MARGIN: f32 = 1.;
+/// One in this many cells is alive in sparse grids, and in dense ones.
+const SPARSE_RATIO: usize = 50;
+const DENSE_RATIO: usize = 3;
+/// The side of the (square) grid drawn in the `drawing` group.
+const DRAWN_GRID_SIZE: usize = 500;
+
+/// Checks whether the cell at the given position is alive in a grid
+/// with one in `ratio` cells alive, scattered without a visible
+/// pattern.
+fn is_alive((x, y): (usize, usize), ratio: usize) -> bool {
+    x.wrapping_mul(7919).wrapping_add(y.wrapping_mul(104_729)) % ratio == 0
+}
+
+/// Returns the live cells of a grid of the given size.
+fn live_cells(size: usize, ratio: usize) -> HashMap<(usize, usize), bool> {
+    (0..size)
+        .flat_map(|y| (0..size).map(move |x| (x, y)))
+        .filter(|&pos| is_alive(pos, ratio))
+        .map(|pos| (pos, true))
+        .collect()
+}
+
+/// Returns the side of a cell in a grid of the given size, as laid out
+/// by the frontends.
+#[allow(clippy::cast_precision_loss)]
+fn cell_size(size: usize) -> f32 {
+    let cells = size as f32;
+
+    (cells + 1.).mul_add(-CELL_MARGIN, WINDOW_SIZE) / cells
+}
+
+fn render(alive: bool) -> Color {
+    if alive {
+        Color::WHITE
+    } else {
+        Color::BLACK
+    }
+}
+
+fn layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layout");
+    let window = Vector2::new(WINDOW_SIZE, WINDOW_SIZE);
+
+    for size in GRID_SIZES {
+        group.bench_with_input(
+            BenchmarkId::new("cell_coordinates", size),
+            &size,
+            |b, &size| {
+                b.iter(|| cell_coordinates(black_box(size), 1, cell_size(size), CELL_MARGIN, 0.));
+            },
+        );
+    }
+    for size in GRID_SIZES {
+        let inputs = LayoutInputs {
+            window_size: (1024, 1024),
+            area: Rectangle::new(0., 0., WINDOW_SIZE, WINDOW_SIZE),
+            grid_size: (size, size),
+            band: 1,
+            margin: 1,
+            detail_thresholds: (3., 1.5),
+            fill_area: false,
+        };
+
+        group.bench_with_input(BenchmarkId::new("lay_out", size), &inputs, |b, inputs| {
+            b.iter(|| lay_out(black_box(inputs)));
+        });
+    }
+    group.bench_function("covers_window", |b| {
+        b.iter(|| {
+            covers_window(
+                black_box(Rectangle::new(0., 0., WINDOW_SIZE, WINDOW_SIZE)),
+                window,
+                0,
+            )
+        });
+    });
+    group.bench_function("fit_camera", |b| {
+        b.iter(|| {
+            fit_camera(
+                black_box((1920, 1080)),
+                Rectangle::new(0., 0., WINDOW_SIZE, WINDOW_SIZE),
+            )
+        });
+    });
+    group.finish();
+}
+
+fn positions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("positions");
+
+    for size in GRID_SIZES {
+        let xs = cell_coordinates(size, 0, cell_size(size), CELL_MARGIN, 0.);
+        let context = OverlayContext {
+            grid_rect: Rectangle::new(0., 0., WINDOW_SIZE, WINDOW_SIZE),
+            origin: Vector2::new(CELL_MARGIN, CELL_MARGIN),
+            cell_size: cell_size(size),
+            cell_margin: CELL_MARGIN,
+        };
+
+        // NOTE: How the generic frontend finds the cells since the
+        // coordinates are cached with the layout
+        group.bench_with_input(BenchmarkId::new("lookup", size), &size, |b, &size| {
+            b.iter(|| {
+                (0..size).for_each(|y| {
+                    (0..size).for_each(|x| {
+                        black_box(Vector2::new(
+                            xs.get(x).copied().unwrap_or_default(),
+                            xs.get(y).copied().unwrap_or_default(),
+                        ));
+                    });
+                });
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("cell_rect", size), &size, |b, &size| {
+            b.iter(|| {
+                (0..size).for_each(|y| {
+                    (0..size).for_each(|x| {
+                        black_box(context.cell_rect((x, y)));
+                    });
+                });
+            });
+        });
+    }
+    group.finish();
+}
+
+fn colors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("colors");
+
+    for size in GRID_SIZES {
+        let grid: Vec<Vec<bool>> = (0..size)
+            .map(|y| (0..size).map(|x| is_alive((x, y), DENSE_RATIO)).collect())
+            .collect();
+
+        // NOTE: The generic frontend records the colors when the grid is
+        // only rendered every few generations
+        group.bench_with_input(BenchmarkId::new("snapshot", size), &grid, |b, grid| {
+            let mut snapshot = GridSnapshot::new();
+            let mut generation = 0;
+
+            snapshot.every = 2;
+            b.iter(|| {
+                generation += 2;
+                snapshot.begin(generation, (false, [size, size, 0, 0]));
+                grid.iter()
+                    .flatten()
+                    .for_each(|&alive| snapshot.record(render(alive)));
+            });
+        });
+
+        for (name, ratio) in [("dense_fill", DENSE_RATIO), ("sparse_fill", SPARSE_RATIO)] {
+            let cells = live_cells(size, ratio);
+
+            group.bench_with_input(BenchmarkId::new(name, size), &cells, |b, cells| {
+                let mut colors = DenseColors::new();
+                let mut generation = 0;
+
+                b.iter(|| {
+                    generation += 1;
+                    colors.fill(
+                        (generation, 0),
+                        (size, size),
+                        cells.iter().map(|(&pos, &alive)| (pos, render(alive))),
+                    );
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+fn draw_loops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("draw_loops");
+
+    for size in GRID_SIZES {
+        let positions = cell_coordinates(size, 0, cell_size(size), CELL_MARGIN, 0.);
+        let position = |x: usize, y: usize| {
+            Vector2::new(
+                positions.get(x).copied().unwrap_or_default(),
+                positions.get(y).copied().unwrap_or_default(),
+            )
+        };
+        let mut sink = Vec::with_capacity(size.saturating_mul(size));
+
+        // NOTE: Like the generic frontend with a background cell color,
+        // which skips the cells of that color
+        let grid: Vec<Vec<bool>> = (0..size)
+            .map(|y| (0..size).map(|x| is_alive((x, y), DENSE_RATIO)).collect())
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("generic", size), &grid, |b, grid| {
+            b.iter(|| {
+                sink.clear();
+                grid.iter().enumerate().for_each(|(y, xs)| {
+                    xs.iter().enumerate().for_each(|(x, &alive)| {
+                        let color = render(alive);
+
+                        if color != Color::BLACK {
+                            sink.push((position(x, y), color));
+                        }
+                    });
+                });
+                black_box(&sink);
+            });
+        });
+
+        // NOTE: Like the life-like frontend, with the colors taken from
+        // the dense buffer if there are enough live cells, and looked up
+        // otherwise
+        for (name, ratio) in [
+            ("life_like_dense", DENSE_RATIO),
+            ("life_like_sparse", SPARSE_RATIO),
+        ] {
+            let cells = live_cells(size, ratio);
+            let mut colors = DenseColors::new();
+
+            colors.fill(
+                (0, 0),
+                (size, size),
+                cells.iter().map(|(&pos, &alive)| (pos, render(alive))),
+            );
+            group.bench_with_input(BenchmarkId::new(name, size), &cells, |b, cells| {
+                b.iter(|| {
+                    sink.clear();
+                    (0..size).for_each(|x| {
+                        (0..size).for_each(|y| {
+                            let color = if ratio == DENSE_RATIO {
+                                colors.color_at((x, y))
+                            } else {
+                                cells.get(&(x, y)).map(|&alive| render(alive))
+                            };
+
+                            sink.push((position(x, y), color.unwrap_or(Color::BLACK)));
+                        });
+                    });
+                    black_box(&sink);
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+fn drawing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("drawing");
+    let (mut rl, thread) = raylib::init()
+        .size(WINDOW_SIZE as i32, WINDOW_SIZE as i32)
+        .title("lifers-raylib benchmarks")
+        .build();
+    let state = rl.get_window_state().set_window_hidden(true);
+
+    rl.set_window_state(state);
+
+    let mut target = rl
+        .load_render_texture(&thread, WINDOW_SIZE as u32, WINDOW_SIZE as u32)
+        .unwrap();
+    let size = cell_size(DRAWN_GRID_SIZE);
+    let positions = cell_coordinates(DRAWN_GRID_SIZE, 0, size, CELL_MARGIN, 0.);
+    let rects: Vec<_> = live_cells(DRAWN_GRID_SIZE, DENSE_RATIO)
+        .into_keys()
+        .map(|(x, y)| {
+            Vector2::new(
+                positions.get(x).copied().unwrap_or_default(),
+                positions.get(y).copied().unwrap_or_default(),
+            )
+        })
+        .collect();
+    let size = Vector2::new(size, size);
+
+    // NOTE: Drawn into a texture like `render_to_image()`, as ending the
+    // texture mode submits the queued vertices
+    group.bench_function(BenchmarkId::new("per_cell", DRAWN_GRID_SIZE), |b| {
+        b.iter(|| {
+            let mut drawer = rl.begin_texture_mode(&thread, &mut target);
+
+            drawer.clear_background(Color::BLACK);
+            rects
+                .iter()
+                .for_each(|&pos| drawer.draw_rectangle_v(pos, size, Color::WHITE));
+        });
+    });
+    group.bench_function(BenchmarkId::new("batched", DRAWN_GRID_SIZE), |b| {
+        let mut batch = RectBatch::default();
+
+        b.iter(|| {
+            let mut drawer = rl.begin_texture_mode(&thread, &mut target);
+
+            drawer.clear_background(Color::BLACK);
+            rects.iter().for_each(|&pos| batch.push(pos, Color::WHITE));
+            batch.flush(&mut drawer, size);
+        });
+    });
+    group.finish();
+}
+
+fn rle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rle");
+
+    for size in GRID_SIZES {
+        let pattern = Pattern::from_cells(live_cells(size, DENSE_RATIO).into_keys());
+        let text = patterns::to_rle(&pattern);
+
+        group.bench_with_input(BenchmarkId::new("encode", size), &pattern, |b, pattern| {
+            b.iter(|| patterns::to_rle(black_box(pattern)));
+        });
+        group.bench_with_input(BenchmarkId::new("parse", size), &text, |b, text| {
+            b.iter(|| patterns::parse_rle(black_box(text)));
+        });
+    }
+    group.finish();
+}
+
+fn screen_conversions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("screen_conversions");
+
+    for size in GRID_SIZES {
+        let context = OverlayContext {
+            grid_rect: Rectangle::new(0., 0., WINDOW_SIZE, WINDOW_SIZE),
+            origin: Vector2::new(CELL_MARGIN, CELL_MARGIN),
+            cell_size: cell_size(size),
+            cell_margin: CELL_MARGIN,
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let points: Vec<_> = (0..1024)
+            .map(|i| Vector2::new((i % 32) as f32 * 32.5, (i / 32) as f32 * 31.7))
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("cell_at", size), &points, |b, points| {
+            b.iter(|| {
+                points
+                    .iter()
+                    .filter_map(|&point| context.cell_at(point))
+                    .count()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    layout,
+    positions,
+    colors,
+    draw_loops,
+    drawing,
+    rle,
+    screen_conversions
+);
+criterion_main!(benches);
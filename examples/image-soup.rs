@@ -0,0 +1,72 @@
+//! Turns a (preferably black-and-white) image into a soup of Conway's
+//! Game of Life, with the dark pixels making up the live cells.
+//!
+//! Run with the path to the image, e.g. `cargo run --example image-soup
+//! -- logo.png`.
+
+use std::{env, process, time::Duration};
+
+use lifers::prelude::*;
+use lifers_raylib::{convert, generic::FrontendBuilder};
+use raylib::color::Color;
+
+const WINDOW_SIZE: (u32, u32) = (1024, 1024);
+const CELLS_N: (usize, usize) = (256, 256);
+const CELL_MARGIN: u32 = 0;
+const UPDATE_RATE: Duration = Duration::from_millis(50);
+// Pixels darker than this become live cells
+const THRESHOLD: u8 = 128;
+
+#[derive(Clone, Copy)]
+struct Cell {
+    is_alive: bool,
+}
+
+impl RenderCell<Color> for Cell {
+    fn render_cell(&self) -> Color {
+        if self.is_alive {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        }
+    }
+}
+
+fn main() {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("usage: image-soup <image>");
+        process::exit(1);
+    };
+
+    let game = generic::Automaton::build(CELLS_N)
+        .init(|_| Cell { is_alive: false })
+        .map(|(x, y), _, cells| generic::count_neighbors(cells, (x, y), 1, |cell| cell.is_alive))
+        .run(|_, cell, neighbors_n| Cell {
+            is_alive: match cell.is_alive {
+                true => (2..=3).contains(&neighbors_n),
+                false => neighbors_n == 3,
+            },
+        });
+
+    let mut frontend = FrontendBuilder::new(WINDOW_SIZE)
+        .cell_margin(CELL_MARGIN)
+        .update_rate(UPDATE_RATE)
+        .finish(game);
+
+    // Start paused to show the image first
+    frontend.pause();
+    if let Err(error) = frontend.load_image_as_cells(&path, |color| {
+        Some(Cell {
+            is_alive: convert::is_dark(color, THRESHOLD),
+        })
+    }) {
+        eprintln!("couldn't load {path}: {error}");
+        process::exit(1);
+    }
+
+    while !frontend.window_should_close() {
+        frontend.display_grid();
+        frontend.default_key_actions();
+        frontend.tick();
+    }
+}
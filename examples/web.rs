@@ -0,0 +1,113 @@
+//! Conway's Game of Life in a web page, driven one frame at a time by
+//! the browser.
+//!
+//! Build it with Emscripten (`emcc` has to be in `PATH`):
+//!
+//! ```sh
+//! rustup target add wasm32-unknown-emscripten
+//! EMCC_CFLAGS="-sUSE_GLFW=3 -sGL_ENABLE_GET_PROC_ADDRESS -sASYNCIFY" \
+//!     cargo build --release --example web --features web \
+//!     --target wasm32-unknown-emscripten
+//! ```
+//!
+//! Then serve `target/wasm32-unknown-emscripten/release/examples/`
+//! (e.g. with `python3 -m http.server`) along with a page loading
+//! `web.js` into a canvas:
+//!
+//! ```html
+//! <canvas id="canvas" oncontextmenu="event.preventDefault()"></canvas>
+//! <script>var Module = { canvas: document.getElementById("canvas") };</script>
+//! <script src="web.js"></script>
+//! ```
+//!
+//! Natively it runs like the other examples.
+
+use std::time::Duration;
+
+use lifers::prelude::*;
+use lifers_raylib::generic::FrontendBuilder;
+use rand::random;
+use raylib::color::Color;
+
+const WINDOW_SIZE: (u32, u32) = (640, 640);
+const CELLS_N: (usize, usize) = (128, 128);
+const CELL_MARGIN: u32 = 1;
+const UPDATE_RATE: Duration = Duration::from_millis(50);
+
+struct Cell {
+    is_alive: bool,
+}
+
+impl RenderCell<Color> for Cell {
+    fn render_cell(&self) -> Color {
+        if self.is_alive {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        }
+    }
+}
+
+#[cfg(target_os = "emscripten")]
+mod browser {
+    use std::{cell::RefCell, os::raw::c_int};
+
+    extern "C" {
+        fn emscripten_set_main_loop(
+            func: extern "C" fn(),
+            fps: c_int,
+            simulate_infinite_loop: c_int,
+        );
+        fn emscripten_cancel_main_loop();
+    }
+
+    thread_local! {
+        // NOTE: The loop outlives `main()`, so the frontend is kept here
+        static FRAME: RefCell<Option<Box<dyn FnMut() -> bool>>> = RefCell::new(None);
+    }
+
+    extern "C" fn run_frame() {
+        let keep_going =
+            FRAME.with(|frame| frame.borrow_mut().as_mut().is_some_and(|frame| frame()));
+
+        if !keep_going {
+            // SAFETY: Called from within the main loop it stops
+            unsafe { emscripten_cancel_main_loop() };
+        }
+    }
+
+    /// Calls `frame` on every animation frame of the browser until it
+    /// returns `false`.
+    pub fn run(frame: impl FnMut() -> bool + 'static) {
+        FRAME.with(|slot| *slot.borrow_mut() = Some(Box::new(frame)));
+
+        // SAFETY: `run_frame` lives as long as the program.  A frame rate
+        // of 0 follows `requestAnimationFrame()`
+        unsafe { emscripten_set_main_loop(run_frame, 0, 1) };
+    }
+}
+
+fn main() {
+    let game = generic::Automaton::build(CELLS_N)
+        .init(|_| Cell {
+            is_alive: random::<bool>(),
+        })
+        .map(|(x, y), _, cells| generic::count_neighbors(cells, (x, y), 1, |cell| cell.is_alive))
+        .run(|_, cell, neighbors_n| Cell {
+            is_alive: match cell.is_alive {
+                true => (2..=3).contains(&neighbors_n),
+                false => neighbors_n == 3,
+            },
+        });
+
+    let mut frontend = FrontendBuilder::new(WINDOW_SIZE)
+        .cell_margin(CELL_MARGIN)
+        .update_rate(UPDATE_RATE)
+        .finish(game);
+
+    #[cfg(target_os = "emscripten")]
+    browser::run(move || frontend.run_frame());
+
+    #[cfg(not(target_os = "emscripten"))]
+    while frontend.run_frame() {}
+}
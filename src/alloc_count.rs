@@ -0,0 +1,67 @@
+//! Counting of heap allocations, to catch the frame path allocating
+//! again once its buffers are warmed up.
+//!
+//! Nothing is counted unless [`CountingAllocator`] is installed as the
+//! global allocator of the program:
+//!
+//! ```ignore
+//! use lifers_raylib::alloc_count::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! The frontends then report the allocations made by drawing the grid
+//! in [`FrameStats::grid_allocations`](crate::hud::FrameStats).  See
+//! `tests/allocations.rs` for a check of it.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+thread_local! {
+    // NOTE: Per thread, so that the stepper thread and the Rayon workers
+    // don't disturb the counts of the drawing thread
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts an allocation on the current thread.
+fn count() {
+    // NOTE: The counter is gone while the thread is being torn down
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get().wrapping_add(1)));
+}
+
+/// The system allocator, counting the allocations (and reallocations)
+/// separately on every thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CountingAllocator;
+
+// SAFETY: Everything is forwarded to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+/// Returns the number of allocations made on the current thread so far
+/// (wrapping around), always zero unless [`CountingAllocator`] is the
+/// global allocator.
+pub fn allocations() -> usize {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or_default()
+}
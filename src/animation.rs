@@ -0,0 +1,99 @@
+//! Recording of generations into animated GIFs.
+
+use std::{fs::File, io::BufWriter, iter, path::Path, time::Duration};
+
+use gif::{Encoder, Frame, Repeat};
+use raylib::color::Color;
+
+/// Trade-off between the quality and the speed of reducing the colors
+/// of a frame to a palette (from 1 to 30, the higher the faster).
+const QUANTIZE_SPEED: i32 = 10;
+/// The shortest delay between frames (in hundredths of a second) that
+/// viewers respect, shorter ones are often slowed down instead.
+const MIN_DELAY: u16 = 2;
+
+/// Writes frames to a GIF file as they come, so that only one of them
+/// is kept in memory.
+pub(crate) struct GifRecorder {
+    encoder: Encoder<BufWriter<File>>,
+    grid_size: (usize, usize),
+    frame_size: (u16, u16),
+    scale: usize,
+    delay: u16,
+    pixels: Vec<u8>,
+}
+
+impl GifRecorder {
+    /// Creates the file for a grid of the given size, with each cell
+    /// taking up `scale` by `scale` pixels and each frame shown for
+    /// `frame_time`.
+    ///
+    /// Fails if the file can't be created or the frames would be larger
+    /// than the format allows.
+    pub fn create(
+        path: &Path,
+        grid_size: (usize, usize),
+        scale: usize,
+        frame_time: Duration,
+    ) -> Result<Self, String> {
+        let scale = scale.max(1);
+        let side = |cells: usize| {
+            cells
+                .checked_mul(scale)
+                .and_then(|pixels| u16::try_from(pixels).ok())
+                .ok_or_else(|| "the GIF would be too large".to_owned())
+        };
+        let frame_size = (side(grid_size.0)?, side(grid_size.1)?);
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut encoder = Encoder::new(BufWriter::new(file), frame_size.0, frame_size.1, &[])
+            .map_err(|e| e.to_string())?;
+
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            encoder,
+            grid_size,
+            frame_size,
+            scale,
+            delay: u16::try_from(frame_time.as_millis() / 10)
+                .unwrap_or(u16::MAX)
+                .max(MIN_DELAY),
+            pixels: Vec::new(),
+        })
+    }
+
+    /// Renders the cells with the colors given by `color_at` and
+    /// appends them as the next frame.
+    pub fn write_frame(
+        &mut self,
+        mut color_at: impl FnMut((usize, usize)) -> Color,
+    ) -> Result<(), String> {
+        let (width, height) = self.grid_size;
+        let scale = self.scale;
+
+        self.pixels.clear();
+        (0..height).for_each(|y| {
+            let row: Vec<u8> = (0..width)
+                .flat_map(|x| {
+                    let Color { r, g, b, a } = color_at((x, y));
+
+                    iter::repeat([r, g, b, a]).take(scale).flatten()
+                })
+                .collect();
+
+            (0..scale).for_each(|_| self.pixels.extend_from_slice(&row));
+        });
+
+        let mut frame = Frame::from_rgba_speed(
+            self.frame_size.0,
+            self.frame_size.1,
+            &mut self.pixels,
+            QUANTIZE_SPEED,
+        );
+        frame.delay = self.delay;
+
+        self.encoder.write_frame(&frame).map_err(|e| e.to_string())
+    }
+}
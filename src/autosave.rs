@@ -0,0 +1,103 @@
+//! Periodic saving of the state into a directory, keeping only the
+//! last few files.
+
+use std::{
+    collections::VecDeque,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::recorder;
+
+/// The start of the names of the saved files.
+const PREFIX: &str = "autosave_";
+/// The extension of the saved files.
+const EXTENSION: &str = "json";
+
+/// Saves a state every few generations, removing the oldest files.
+///
+/// The files are named after the generation (`autosave_000000000100.json`),
+/// so that they sort in the order they were saved.  Files left in the
+/// directory by earlier runs count towards the limit as well.
+pub(crate) struct Autosave<T> {
+    dir: PathBuf,
+    every: u64,
+    keep_last: usize,
+    last: Option<u64>,
+    saved: VecDeque<PathBuf>,
+    save: fn(&T, &Path) -> Result<(), String>,
+}
+
+impl<T> Autosave<T> {
+    /// Starts saving into `dir` every `every` generations after the
+    /// given one with `save`, keeping at most `keep_last` files (at
+    /// least one).
+    ///
+    /// Fails if the directory can't be created or read.
+    pub fn start(
+        dir: PathBuf,
+        every: u64,
+        keep_last: usize,
+        generation: u64,
+        save: fn(&T, &Path) -> Result<(), String>,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        let mut saved: Vec<_> = fs::read_dir(&dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_autosave(path))
+            .collect();
+
+        saved.sort();
+
+        Ok(Self {
+            dir,
+            every,
+            keep_last: keep_last.max(1),
+            last: Some(generation),
+            saved: saved.into(),
+            save,
+        })
+    }
+
+    /// Checks whether a state should be saved at the given generation.
+    pub fn is_due(&self, generation: u64) -> bool {
+        recorder::is_due(self.last, self.every, generation)
+    }
+
+    /// Saves the state at the given generation, then removes the oldest
+    /// files over the limit.
+    ///
+    /// Fails if the state can't be saved or an old file can't be
+    /// removed.
+    pub fn save(&mut self, target: &T, generation: u64) -> Result<(), String> {
+        let path = self
+            .dir
+            .join(format!("{PREFIX}{generation:012}.{EXTENSION}"));
+
+        self.last = Some(generation);
+        (self.save)(target, &path)?;
+        if !self.saved.contains(&path) {
+            self.saved.push_back(path);
+        }
+
+        while self.saved.len() > self.keep_last {
+            if let Some(old) = self.saved.pop_front() {
+                fs::remove_file(&old).map_err(|error| format!("{}: {error}", old.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks whether a file was written by [`Autosave`].
+fn is_autosave(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == EXTENSION)
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(PREFIX))
+}
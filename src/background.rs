@@ -0,0 +1,109 @@
+//! Coloring of empty ("dead") cells.
+
+use std::ops::Range;
+
+use raylib::color::Color;
+
+/// Describes how empty cells are colored.
+///
+/// In the [`life_like`](crate::life_like) frontend it's used for
+/// every position that has no cell.  In the
+/// [`generic`](crate::generic) frontend it's used for cells that
+/// render as fully transparent (e.g. [`Color::BLANK`]).
+#[non_exhaustive]
+pub enum Background {
+    /// A single color for every position.
+    Solid(Color),
+    /// Alternating colors, starting with the first one at `(0, 0)`.
+    Checkerboard(Color, Color),
+    /// An arbitrary color for each position.
+    Custom(Box<dyn Fn((usize, usize)) -> Color>),
+}
+
+impl Background {
+    /// Creates a [`Background::Custom`] from the given closure.
+    pub fn custom<F: Fn((usize, usize)) -> Color + 'static>(f: F) -> Self {
+        Self::Custom(Box::new(f))
+    }
+
+    /// Returns the color of an empty cell at the given position.
+    pub fn color_at(&self, (x, y): (usize, usize)) -> Color {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Checkerboard(even, odd) => {
+                if (x ^ y) & 1 == 0 {
+                    *even
+                } else {
+                    *odd
+                }
+            }
+            Self::Custom(f) => f((x, y)),
+        }
+    }
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+/// Colors of the empty cells in a visible window of the grid,
+/// computed once and reused as long as the window doesn't move.
+///
+/// Evaluating a [`Background::Custom`] closure for every position each
+/// frame can get expensive, so it's assumed to only depend on the
+/// position.
+#[derive(Default)]
+pub(crate) struct BackgroundCache {
+    xs: Range<usize>,
+    ys: Range<usize>,
+    colors: Vec<Color>,
+}
+
+impl BackgroundCache {
+    /// Returns the background color at the given position, which must
+    /// be within the last window passed to
+    /// [`update()`](Self::update()).
+    pub fn color_at(&self, (x, y): (usize, usize)) -> Option<Color> {
+        if !self.xs.contains(&x) || !self.ys.contains(&y) {
+            return None;
+        }
+
+        let column = x.checked_sub(self.xs.start)?;
+        let row = y.checked_sub(self.ys.start)?;
+
+        self.colors
+            .get(row.checked_mul(self.xs.len())?.checked_add(column)?)
+            .copied()
+    }
+
+    /// Recomputes the colors if the visible window changed.
+    ///
+    /// Only [`Background::Custom`] is cached, the other backgrounds are
+    /// as cheap to compute as to look up, so
+    /// [`color_at()`](Self::color_at()) returns [`None`] for them.
+    pub fn update(&mut self, background: &Background, xs: &Range<usize>, ys: &Range<usize>) {
+        if !matches!(background, Background::Custom(_)) {
+            self.colors.clear();
+            return;
+        }
+        if self.xs == *xs && self.ys == *ys && !self.colors.is_empty() {
+            return;
+        }
+
+        self.xs = xs.clone();
+        self.ys = ys.clone();
+        self.colors.clear();
+        self.colors.extend(
+            ys.clone()
+                .flat_map(|y| xs.clone().map(move |x| background.color_at((x, y)))),
+        );
+    }
+
+    /// Forces the colors to be recomputed on the next
+    /// [`update()`](Self::update()).
+    pub fn invalidate(&mut self) {
+        self.colors.clear();
+    }
+}
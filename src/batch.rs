@@ -0,0 +1,73 @@
+//! Batched drawing of cells through `rlgl`.
+//!
+//! Drawing every cell with [`RaylibDraw::draw_rectangle_v()`] goes
+//! through the whole shape drawing machinery of Raylib once per cell,
+//! which dominates the frame time on large grids.  [`RectBatch`]
+//! instead collects the cells and submits them as plain quads.
+
+use raylib::{color::Color, drawing::RaylibDraw, ffi, math::Vector2};
+
+/// Grids with at least this many visible cells are drawn in batches.
+pub const BATCH_THRESHOLD: usize = 4096;
+
+/// `RL_QUADS` from `rlgl.h`.
+const RL_QUADS: i32 = 0x0007;
+
+/// The number of quads submitted between checks of the `rlgl` batch
+/// limit.
+///
+/// Must stay well below `RL_DEFAULT_BATCH_BUFFER_ELEMENTS`.
+const CHUNK_SIZE: usize = 1024;
+
+/// Same-sized rectangles waiting to be drawn.
+#[derive(Default)]
+pub struct RectBatch {
+    rects: Vec<(Vector2, Color)>,
+}
+
+impl RectBatch {
+    /// Queues a rectangle at the given position.
+    pub fn push(&mut self, pos: Vector2, color: Color) {
+        self.rects.push((pos, color));
+    }
+
+    /// Draws all the queued rectangles with the given size, leaving the
+    /// batch empty.
+    ///
+    /// The result is the same as calling
+    /// [`RaylibDraw::draw_rectangle_v()`] for each of them in order.
+    // NOTE: The drawer isn't used, `rlgl` draws into whatever target the
+    // active draw scope set up, so it's only taken to prove that there is one
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub fn flush(&mut self, _drawer: &mut impl RaylibDraw, size: Vector2) {
+        // SAFETY: A drawing handle is borrowed, so we're between
+        // `BeginDrawing()` and `EndDrawing()`, and the batch limit is
+        // checked before every chunk.
+        unsafe {
+            ffi::rlSetTexture(ffi::rlGetTextureIdDefault());
+
+            self.rects.chunks(CHUNK_SIZE).for_each(|chunk| {
+                ffi::rlCheckRenderBatchLimit(chunk.len().saturating_mul(4) as i32);
+                ffi::rlBegin(RL_QUADS);
+                ffi::rlNormal3f(0., 0., 1.);
+                ffi::rlTexCoord2f(0., 0.);
+
+                chunk.iter().for_each(|&(pos, color)| {
+                    let (right, bottom) = (pos.x + size.x, pos.y + size.y);
+
+                    ffi::rlColor4ub(color.r, color.g, color.b, color.a);
+                    ffi::rlVertex2f(pos.x, pos.y);
+                    ffi::rlVertex2f(pos.x, bottom);
+                    ffi::rlVertex2f(right, bottom);
+                    ffi::rlVertex2f(right, pos.y);
+                });
+
+                ffi::rlEnd();
+            });
+
+            ffi::rlSetTexture(0);
+        }
+
+        self.rects.clear();
+    }
+}
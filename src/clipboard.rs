@@ -0,0 +1,56 @@
+//! Access to the clipboard, which patterns are copied to and pasted
+//! from.
+//!
+//! The frontends go through the [`Clipboard`] trait, so that the
+//! system clipboard can be replaced, e.g. with a [`MemoryClipboard`]
+//! to keep the copied patterns within the program (or in tests).
+
+use raylib::RaylibHandle;
+
+/// A clipboard holding text.
+pub trait Clipboard {
+    /// Returns the text on the clipboard, or [`None`] if it doesn't
+    /// hold any.
+    fn text(&mut self, rl: &RaylibHandle) -> Option<String>;
+
+    /// Puts text on the clipboard, returning whether it worked.
+    fn set_text(&mut self, rl: &mut RaylibHandle, text: &str) -> bool;
+}
+
+/// The clipboard of the system, accessed through raylib (the default).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn text(&mut self, rl: &RaylibHandle) -> Option<String> {
+        rl.get_clipboard_text().ok()
+    }
+
+    fn set_text(&mut self, rl: &mut RaylibHandle, text: &str) -> bool {
+        rl.set_clipboard_text(text).is_ok()
+    }
+}
+
+/// A clipboard kept in memory, not shared with other programs.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl MemoryClipboard {
+    /// Creates an empty clipboard.
+    pub const fn new() -> Self {
+        Self { text: None }
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn text(&mut self, _rl: &RaylibHandle) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, _rl: &mut RaylibHandle, text: &str) -> bool {
+        self.text = Some(text.to_owned());
+        true
+    }
+}
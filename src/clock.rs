@@ -0,0 +1,64 @@
+//! The clock the timers are based on.
+//!
+//! It's [`std::time::Instant`], except with the `web` feature on
+//! Emscripten, where the browser's clock (`performance.now()`) is used
+//! instead.
+
+#[cfg(not(all(feature = "web", target_os = "emscripten")))]
+pub use std::time::Instant;
+
+#[cfg(all(feature = "web", target_os = "emscripten"))]
+pub use web::Instant;
+
+#[cfg(all(feature = "web", target_os = "emscripten"))]
+mod web {
+    use std::time::Duration;
+
+    extern "C" {
+        /// Returns the milliseconds passed since the page was loaded.
+        fn emscripten_get_now() -> f64;
+    }
+
+    /// A point in time of the browser's clock, which is monotonic.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    pub struct Instant(f64);
+
+    impl Instant {
+        /// Returns the current time.
+        pub fn now() -> Self {
+            // SAFETY: It only reads the clock
+            Self(unsafe { emscripten_get_now() })
+        }
+
+        /// Returns the time passed since `earlier`, or [`None`] if it's
+        /// later than this one.
+        pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+            Duration::try_from_secs_f64((self.0 - earlier.0) / 1000.).ok()
+        }
+
+        /// Returns the time passed since `earlier`, or zero if it's
+        /// later than this one.
+        pub fn duration_since(&self, earlier: Self) -> Duration {
+            self.checked_duration_since(earlier).unwrap_or_default()
+        }
+
+        /// Returns the time passed since `earlier`, or zero if it's
+        /// later than this one (same as [`Instant::duration_since()`]).
+        pub fn saturating_duration_since(&self, earlier: Self) -> Duration {
+            self.duration_since(earlier)
+        }
+
+        /// Returns the time the given duration after this one, or
+        /// [`None`] if it can't be represented.
+        pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+            let time = self.0 + duration.as_secs_f64() * 1000.;
+
+            time.is_finite().then_some(Self(time))
+        }
+
+        /// Returns the time passed since this one.
+        pub fn elapsed(&self) -> Duration {
+            Self::now().duration_since(*self)
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Color interpolation helpers.
+//!
+//! Interpolating each channel of an sRGB color directly gives dark,
+//! muddy midpoints, so most of the time it's better to mix colors in
+//! linear light (see [`lerp_linear()`]).
+
+use raylib::color::Color;
+
+/// The color space in which colors are mixed.
+///
+/// Defaults to [`Srgb`](Self::Srgb), even though [`Linear`](Self::Linear)
+/// gives better midpoints: raylib blends translucent colors in sRGB,
+/// so mixing them the same way keeps the frontend's own blending
+/// consistent with what's drawn over it, and it's cheaper.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Mix the (gamma-encoded) sRGB channels directly.
+    ///
+    /// Cheaper, but midpoints come out darker than expected.
+    #[default]
+    Srgb,
+    /// Convert to linear light, mix and convert back.
+    Linear,
+}
+
+impl ColorSpace {
+    /// Interpolates between two colors in this color space.
+    ///
+    /// See [`lerp_srgb()`] and [`lerp_linear()`].
+    pub fn lerp(self, from: Color, to: Color, t: f32) -> Color {
+        match self {
+            Self::Srgb => lerp_srgb(from, to, t),
+            Self::Linear => lerp_linear(from, to, t),
+        }
+    }
+}
+
+/// Converts an sRGB channel value (`0.0..=1.0`) to linear light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light channel value (`0.0..=1.0`) to sRGB.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055f32.mul_add(value.powf(1. / 2.4), -0.055)
+    }
+}
+
+/// Interpolates between the sRGB channels of two colors.
+///
+/// `t` is clamped to `0.0..=1.0`, where `0.0` gives `from` and `1.0`
+/// gives `to`.
+pub fn lerp_srgb(from: Color, to: Color, t: f32) -> Color {
+    mix_channels(from, to, t, |from, to, t| lerp(from, to, t))
+}
+
+/// Interpolates between two colors in linear light.
+///
+/// `t` is clamped to `0.0..=1.0`, where `0.0` gives `from` and `1.0`
+/// gives `to`.  Alpha is always interpolated linearly, as it isn't
+/// gamma-encoded.
+pub fn lerp_linear(from: Color, to: Color, t: f32) -> Color {
+    let Color { a, .. } = lerp_srgb(from, to, t);
+
+    Color {
+        a,
+        ..mix_channels(from, to, t, |from, to, t| {
+            linear_to_srgb(lerp(srgb_to_linear(from), srgb_to_linear(to), t))
+        })
+    }
+}
+
+/// Samples a palette at `t` (clamped to `0.0..=1.0`), interpolating
+/// between adjacent colors, which are spread out evenly.
+///
+/// Returns [`None`] if the palette is empty.
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn sample_palette(palette: &[Color], t: f32, space: ColorSpace) -> Option<Color> {
+    let last = palette.len().checked_sub(1)?;
+    let position = t.clamp(0., 1.) * last as f32;
+    let index = (position.floor() as usize).min(last);
+
+    Some(match palette.get(index.saturating_add(1)) {
+        Some(&next) => space.lerp(palette[index], next, position - index as f32),
+        None => palette[index],
+    })
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    (to - from).mul_add(t, from)
+}
+
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn mix_channels(from: Color, to: Color, t: f32, f: impl Fn(f32, f32, f32) -> f32) -> Color {
+    let t = t.clamp(0., 1.);
+    let channel = |from: u8, to: u8| {
+        let value = f(f32::from(from) / 255., f32::from(to) / 255., t);
+
+        (value.clamp(0., 1.) * 255.).round() as u8
+    };
+
+    Color::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+        channel(from.a, to.a),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACES: [ColorSpace; 2] = [ColorSpace::Srgb, ColorSpace::Linear];
+    const COLORS: [Color; 4] = [
+        Color::new(0, 0, 0, 255),
+        Color::new(255, 255, 255, 0),
+        Color::new(12, 200, 99, 128),
+        Color::new(250, 3, 180, 7),
+    ];
+
+    #[test]
+    fn conversion_round_trip() {
+        for value in 0..=u8::MAX {
+            let value = f32::from(value) / 255.;
+
+            assert!((linear_to_srgb(srgb_to_linear(value)) - value).abs() < 1e-4);
+            assert!((srgb_to_linear(linear_to_srgb(value)) - value).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn conversion_endpoints() {
+        for convert in [srgb_to_linear, linear_to_srgb] {
+            assert!(convert(0.).abs() < 1e-6);
+            assert!((convert(1.) - 1.).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn lerp_endpoints() {
+        for space in SPACES {
+            for from in COLORS {
+                for to in COLORS {
+                    assert_eq!(space.lerp(from, to, 0.), from, "{space:?}");
+                    assert_eq!(space.lerp(from, to, 1.), to, "{space:?}");
+                    // NOTE: `t` is clamped
+                    assert_eq!(space.lerp(from, to, -1.), from, "{space:?}");
+                    assert_eq!(space.lerp(from, to, 2.), to, "{space:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lerp_midpoint() {
+        let (black, white) = (Color::BLACK, Color::WHITE);
+
+        assert_eq!(
+            ColorSpace::Srgb.lerp(black, white, 0.5),
+            Color::new(128, 128, 128, 255)
+        );
+        // NOTE: Half the light is brighter than the sRGB midpoint
+        assert_eq!(
+            ColorSpace::Linear.lerp(black, white, 0.5),
+            Color::new(188, 188, 188, 255)
+        );
+    }
+
+    #[test]
+    fn sample_palette() {
+        let palette = [Color::RED, Color::GREEN, Color::BLUE];
+
+        assert_eq!(super::sample_palette(&[], 0.5, ColorSpace::Srgb), None);
+        for space in SPACES {
+            assert_eq!(super::sample_palette(&palette, 0., space), Some(Color::RED));
+            assert_eq!(
+                super::sample_palette(&palette, 0.5, space),
+                Some(Color::GREEN)
+            );
+            assert_eq!(
+                super::sample_palette(&palette, 1., space),
+                Some(Color::BLUE)
+            );
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Conversion of cells between the dense grids of generic automata
+//! and the sparse maps of life-like ones, and from images.
+//!
+//! ```ignore
+//! # use lifers_raylib::convert;
+//! let grid = vec![vec![false, true], vec![true, false]];
+//! let sparse = convert::to_sparse(&grid, |&alive| alive);
+//! let dense = convert::to_dense(sparse, (2, 2), |_| false);
+//!
+//! assert_eq!(grid, dense);
+//! ```
+
+use std::{collections::HashMap, path::Path};
+
+use raylib::{color::Color, texture::Image};
+
+/// Colors with a lower alpha are never considered dark (see
+/// [`is_dark()`]).
+const OPAQUE_THRESHOLD: u8 = 128;
+
+/// Collects the alive cells of a grid (indexed as `grid[y][x]`) into a
+/// map from their positions (`(x, y)`).
+pub fn to_sparse<S, F>(grid: &[Vec<S>], is_alive: F) -> HashMap<(usize, usize), S>
+where
+    S: Clone,
+    F: Fn(&S) -> bool,
+{
+    grid.iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, cell)| is_alive(cell))
+                .map(move |(x, cell)| ((x, y), cell.clone()))
+        })
+        .collect()
+}
+
+/// Places the given cells into a grid (indexed as `grid[y][x]`) of the
+/// given size, filling the rest of it with the cells made by `dead`.
+///
+/// Cells outside of the grid are left out.
+pub fn to_dense<S, F>(
+    cells: impl IntoIterator<Item = ((usize, usize), S)>,
+    (width, height): (usize, usize),
+    mut dead: F,
+) -> Vec<Vec<S>>
+where
+    F: FnMut((usize, usize)) -> S,
+{
+    let mut grid: Vec<Vec<S>> = (0..height)
+        .map(|y| (0..width).map(|x| dead((x, y))).collect())
+        .collect();
+
+    cells.into_iter().for_each(|((x, y), cell)| {
+        if let Some(target) = grid.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *target = cell;
+        }
+    });
+
+    grid
+}
+
+/// Loads an image file (e.g. PNG or BMP) and samples the color of each
+/// cell of a grid of the given size from it, stretching the image to
+/// fit (nearest-neighbor).
+///
+/// The colors are indexed as `grid[y][x]`.  Fails if the image can't
+/// be loaded (e.g. its format isn't supported), or if either it or the
+/// grid is empty.
+pub fn image_to_grid(
+    path: impl AsRef<Path>,
+    (width, height): (usize, usize),
+) -> Result<Vec<Vec<Color>>, String> {
+    let path = path.as_ref();
+    let file = path
+        .to_str()
+        .ok_or_else(|| format!("invalid path: {}", path.display()))?;
+    let image = Image::load_image(file)?;
+    let image_size = (
+        usize::try_from(image.width()).unwrap_or(0),
+        usize::try_from(image.height()).unwrap_or(0),
+    );
+
+    if image_size.0 == 0 || image_size.1 == 0 {
+        return Err(format!("{} is empty", path.display()));
+    }
+    if width == 0 || height == 0 {
+        return Err("the grid is empty".to_owned());
+    }
+
+    let pixels = image.get_image_data();
+    let sample = |cell: usize, cells: usize, pixels: usize| {
+        cell.saturating_mul(pixels).checked_div(cells).unwrap_or(0)
+    };
+
+    Ok((0..height)
+        .map(|y| {
+            let row = sample(y, height, image_size.1).saturating_mul(image_size.0);
+
+            (0..width)
+                .map(|x| {
+                    pixels
+                        .get(row.saturating_add(sample(x, width, image_size.0)))
+                        .copied()
+                        .unwrap_or(Color::BLANK)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Checks whether a color is darker than `threshold` (from `0` to
+/// `255`), e.g. to make cells out of the dark pixels of an image.
+///
+/// Mostly transparent colors are never dark.
+pub fn is_dark(color: Color, threshold: u8) -> bool {
+    let luminance = 0.114f32.mul_add(
+        f32::from(color.b),
+        0.299f32.mul_add(f32::from(color.r), 0.587 * f32::from(color.g)),
+    );
+
+    color.a >= OPAQUE_THRESHOLD && luminance < f32::from(threshold)
+}
+
+/// Makes cells out of a grid of colors (indexed as `grid[y][x]`) with
+/// `mapper`, along with their positions, leaving out the ones it
+/// returns [`None`] for.
+pub fn map_colors<S>(
+    colors: Vec<Vec<Color>>,
+    mut mapper: impl FnMut(Color) -> Option<S>,
+) -> Vec<((usize, usize), S)> {
+    let mut cells = Vec::new();
+
+    colors.into_iter().enumerate().for_each(|(y, row)| {
+        row.into_iter().enumerate().for_each(|(x, color)| {
+            if let Some(cell) = mapper(color) {
+                cells.push(((x, y), cell));
+            }
+        });
+    });
+
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// A glider, indexed as `GLIDER[y][x]`.
+    const GLIDER: [[bool; 4]; 3] = [
+        [false, true, false, false],
+        [false, false, true, false],
+        [true, true, true, false],
+    ];
+
+    fn glider() -> Vec<Vec<bool>> {
+        GLIDER.map(Vec::from).into()
+    }
+
+    #[test]
+    fn sparse_round_trip() {
+        let grid = glider();
+        let sparse = to_sparse(&grid, |&alive| alive);
+        let mut positions: Vec<_> = sparse.keys().copied().collect();
+
+        positions.sort_unstable_by_key(|&(x, y)| (y, x));
+        assert_eq!(positions, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(to_dense(sparse, (4, 3), |_| false), grid);
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let cells: HashMap<_, _> = [((0, 0), 1_u8), ((3, 1), 2), ((2, 2), 3)].into();
+        let grid = to_dense(cells.clone(), (4, 3), |_| 0);
+
+        assert_eq!(grid, [[1, 0, 0, 0], [0, 0, 0, 2], [0, 0, 3, 0]]);
+        assert_eq!(to_sparse(&grid, |&cell| cell != 0), cells);
+    }
+
+    #[test]
+    fn to_dense_outside() {
+        let grid = to_dense(
+            [((1, 0), true), ((4, 0), true), ((0, 3), true)],
+            (2, 2),
+            |_| false,
+        );
+
+        assert_eq!(grid, [[false, true], [false, false]]);
+    }
+
+    #[test]
+    fn to_dense_dead_positions() {
+        assert_eq!(
+            to_dense([], (2, 2), |pos| pos),
+            [[(0, 0), (1, 0)], [(0, 1), (1, 1)]]
+        );
+    }
+
+    #[test]
+    fn image_round_trip() {
+        let path = env::temp_dir().join("lifers-raylib-convert-round-trip.png");
+        let mut image = Image::gen_image_color(8, 6, Color::WHITE);
+        let pixel = |pos: usize| i32::try_from(pos.saturating_mul(2)).unwrap();
+
+        // NOTE: The glider, with cells of 2x2 pixels
+        for (x, y) in to_sparse(&glider(), |&alive| alive).into_keys() {
+            image.draw_rectangle(pixel(x), pixel(y), 2, 2, Color::BLACK);
+        }
+        assert!(path.to_str().is_some_and(|file| image.export_image(file)));
+
+        let colors = image_to_grid(&path, (4, 3));
+        let _ = std::fs::remove_file(&path);
+        let cells = map_colors(colors.unwrap(), |color| is_dark(color, 128).then_some(true));
+
+        assert_eq!(to_dense(cells, (4, 3), |_| false), glider());
+    }
+
+    #[test]
+    fn image_to_grid_missing() {
+        assert!(image_to_grid(env::temp_dir().join("lifers-raylib-missing.png"), (4, 3)).is_err());
+    }
+
+    #[test]
+    fn dark_colors() {
+        assert!(is_dark(Color::BLACK, 128));
+        assert!(!is_dark(Color::WHITE, 128));
+        assert!(!is_dark(Color::new(0, 0, 0, 0), 128));
+        assert!(!is_dark(Color::BLACK, 0));
+    }
+}
@@ -0,0 +1,183 @@
+//! A rolling graph of a statistic over generations.
+
+use std::collections::VecDeque;
+
+use raylib::{drawing::RaylibDraw, math::Vector2};
+
+use crate::hud::{Corner, HudStack, HudStyle, PADDING};
+
+/// The default number of samples kept.
+pub(crate) const DEFAULT_GRAPH_LENGTH: usize = 300;
+/// Size of the whole graph box.
+const GRAPH_SIZE: (i32, i32) = (260, 140);
+/// Length of the axis ticks.
+const TICK_LENGTH: f32 = 4.;
+/// The smallest font size of the tick labels.
+const MIN_LABEL_SIZE: i32 = 10;
+
+/// The last samples of a statistic along with the generations they
+/// were taken at.
+pub(crate) struct Graph {
+    samples: VecDeque<(u64, f32)>,
+    length: usize,
+    pub corner: Corner,
+}
+
+impl Graph {
+    /// Creates an empty graph keeping at most `length` samples.
+    pub fn new(length: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(length),
+            length,
+            corner: Corner::BottomLeft,
+        }
+    }
+
+    /// Appends a sample, dropping the oldest one if the graph is full.
+    pub fn push(&mut self, generation: u64, value: f32) {
+        if self.length == 0 {
+            return;
+        }
+        if self.samples.len() >= self.length {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back((generation, value));
+    }
+
+    /// Removes the samples taken after the given generation.
+    pub fn truncate_after(&mut self, generation: u64) {
+        while self
+            .samples
+            .back()
+            .is_some_and(|&(sample_generation, _)| sample_generation > generation)
+        {
+            self.samples.pop_back();
+        }
+    }
+
+    /// Removes all the samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Draws the samples as a polyline scaled to their range, with
+    /// ticks at both ends of the axes.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub fn draw(&self, drawer: &mut impl RaylibDraw, stack: &mut HudStack) {
+        let (Some(&(first_generation, _)), Some(&(last_generation, _))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return;
+        };
+
+        let HudStyle {
+            font_size,
+            text_color,
+            ..
+        } = stack.style;
+        let label_size = font_size
+            .checked_div(2)
+            .unwrap_or(MIN_LABEL_SIZE)
+            .max(MIN_LABEL_SIZE);
+        let (min, max) = self.samples.iter().fold(
+            (f32::INFINITY, f32::NEG_INFINITY),
+            |(min, max), &(_, value)| (min.min(value), max.max(value)),
+        );
+        // NOTE: A flat line is drawn in the middle
+        let (min, max) = if max - min < f32::EPSILON {
+            (min - 1., max + 1.)
+        } else {
+            (min, max)
+        };
+        let [min_label, max_label, first_label, last_label] = [
+            format!("{min:.0}"),
+            format!("{max:.0}"),
+            first_generation.to_string(),
+            last_generation.to_string(),
+        ];
+        let renderer = stack.renderer;
+        let labels_width = renderer
+            .measure(&min_label, label_size)
+            .max(renderer.measure(&max_label, label_size))
+            .saturating_add(PADDING);
+
+        let (x, y) = stack.place(self.corner, GRAPH_SIZE);
+        stack.draw_box(drawer, (x, y), GRAPH_SIZE);
+
+        let padding = PADDING as f32;
+        let left = (x.saturating_add(labels_width) as f32) + padding;
+        let top = y as f32 + padding;
+        let right = (x.saturating_add(GRAPH_SIZE.0) as f32) - padding;
+        let bottom =
+            (y.saturating_add(GRAPH_SIZE.1).saturating_sub(label_size) as f32) - 2. * padding;
+        let last_index = self.samples.len().saturating_sub(1).max(1) as f32;
+        let point = |index: usize, value: f32| {
+            Vector2::new(
+                (index as f32 / last_index).mul_add(right - left, left),
+                ((value - min) / (max - min)).mul_add(top - bottom, bottom),
+            )
+        };
+
+        drawer.draw_line_v(
+            Vector2::new(left, top),
+            Vector2::new(left, bottom),
+            text_color,
+        );
+        drawer.draw_line_v(
+            Vector2::new(left, bottom),
+            Vector2::new(right, bottom),
+            text_color,
+        );
+        [(top, &max_label), (bottom, &min_label)]
+            .iter()
+            .for_each(|&(tick_y, label)| {
+                drawer.draw_line_v(
+                    Vector2::new(left - TICK_LENGTH, tick_y),
+                    Vector2::new(left, tick_y),
+                    text_color,
+                );
+                renderer.draw(
+                    drawer,
+                    label,
+                    x.saturating_add(PADDING),
+                    (tick_y as i32).saturating_sub(label_size.checked_div(2).unwrap_or(0)),
+                    label_size,
+                    text_color,
+                );
+            });
+        [(left, &first_label), (right, &last_label)]
+            .iter()
+            .for_each(|&(tick_x, label)| {
+                drawer.draw_line_v(
+                    Vector2::new(tick_x, bottom),
+                    Vector2::new(tick_x, bottom + TICK_LENGTH),
+                    text_color,
+                );
+                renderer.draw(
+                    drawer,
+                    label,
+                    (tick_x as i32).saturating_sub(
+                        renderer
+                            .measure(label, label_size)
+                            .checked_div(2)
+                            .unwrap_or(0),
+                    ),
+                    (bottom + padding) as i32,
+                    label_size,
+                    text_color,
+                );
+            });
+
+        let points: Vec<_> = self
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(index, &(_, value))| point(index, value))
+            .collect();
+
+        points
+            .windows(2)
+            .for_each(|line| drawer.draw_line_v(line[0], line[1], text_color));
+    }
+}
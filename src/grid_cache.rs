@@ -0,0 +1,185 @@
+//! Keeping the drawn grid between generations, so that frames in which
+//! nothing changed don't draw every cell again.
+//!
+//! Raylib doesn't allow presenting the previous frame again (the back
+//! buffer is undefined after swapping), so the grid is drawn into a
+//! texture the size of the window instead, which is copied onto the
+//! window every frame.
+//!
+//! Raylib scales what's drawn on the window to the framebuffer on
+//! HiDPI displays, but not what's drawn into textures, so the texture
+//! is sized in framebuffer pixels and scaled here.
+
+use raylib::{
+    color::Color,
+    drawing::RaylibDraw,
+    ffi,
+    math::{Rectangle, Vector2},
+    texture::RenderTexture2D,
+    RaylibHandle, RaylibThread,
+};
+
+/// `RL_ONE` from `rlgl.h`.
+const RL_ONE: i32 = 1;
+/// `RL_SRC_ALPHA` from `rlgl.h`.
+const RL_SRC_ALPHA: i32 = 0x0302;
+/// `RL_ONE_MINUS_SRC_ALPHA` from `rlgl.h`.
+const RL_ONE_MINUS_SRC_ALPHA: i32 = 0x0303;
+/// `RL_FUNC_ADD` from `rlgl.h`.
+const RL_FUNC_ADD: i32 = 0x8006;
+/// `BLEND_ALPHA` from `raylib.h`.
+const BLEND_ALPHA: i32 = 0;
+/// `BLEND_CUSTOM_SEPARATE` from `raylib.h`.
+const BLEND_CUSTOM_SEPARATE: i32 = 7;
+
+/// The grid as it was last drawn, along with a key describing what was
+/// drawn (e.g. the generation and the view).
+///
+/// The grid has to be drawn again whenever the key changes or the cache
+/// is invalidated.
+pub(crate) struct GridCache<K> {
+    /// Whether the cache is used at all.
+    pub enabled: bool,
+    target: Option<RenderTexture2D>,
+    /// The size of the window, in logical units.
+    size: (i32, i32),
+    /// The ratio of the framebuffer size to the window size.
+    scale: f32,
+    drawn: Option<K>,
+    /// Whether the cache is being drawn into.
+    drawing: bool,
+}
+
+impl<K: PartialEq> GridCache<K> {
+    /// Creates an empty cache, the texture is only created on the first
+    /// [`prepare()`](Self::prepare()).
+    pub const fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            target: None,
+            size: (0, 0),
+            scale: 1.,
+            drawn: None,
+            drawing: false,
+        }
+    }
+
+    /// Makes sure the texture fits a window of the given size (in
+    /// logical units) at the given DPI scale, recreating it (and so
+    /// invalidating the cache) if necessary.
+    ///
+    /// Fails if the texture can't be created.
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::float_cmp
+    )]
+    pub fn prepare(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        size: (i32, i32),
+        scale: f32,
+    ) -> Result<(), String> {
+        if self.target.is_some() && self.size == size && self.scale == scale {
+            return Ok(());
+        }
+
+        let scaled = |side: i32| (side.max(1) as f32 * scale).round().max(1.) as i32;
+        let width = u32::try_from(scaled(size.0)).map_err(|e| e.to_string())?;
+        let height = u32::try_from(scaled(size.1)).map_err(|e| e.to_string())?;
+
+        // NOTE: The old texture is dropped first, there's no need for two
+        self.target = None;
+        self.drawn = None;
+        self.target = Some(rl.load_render_texture(thread, width, height)?);
+        self.size = size;
+        self.scale = scale;
+
+        Ok(())
+    }
+
+    /// Checks whether the cache holds the grid drawn with the given key.
+    pub fn is_clean(&self, key: &K) -> bool {
+        self.target.is_some() && self.drawn.as_ref() == Some(key)
+    }
+
+    /// Makes the grid be drawn again on the next frame, e.g. after the
+    /// colors changed.
+    pub fn invalidate(&mut self) {
+        self.drawn = None;
+    }
+
+    /// Redirects everything drawn with `drawer` into the cache until
+    /// [`end()`](Self::end()), marking it as drawn with the given key.
+    ///
+    /// The translucent parts are blended so that the cache stays as
+    /// opaque as what it's drawn over.
+    pub fn begin(&mut self, _drawer: &mut impl RaylibDraw, key: K) {
+        let Some(target) = &self.target else {
+            return;
+        };
+
+        // SAFETY: A drawing handle is borrowed, so we're between
+        // `BeginDrawing()` and `EndDrawing()`, and the texture outlives
+        // the redirection (see `end()`)
+        unsafe {
+            ffi::BeginTextureMode(*target.as_ref());
+            ffi::rlSetBlendFactorsSeparate(
+                RL_SRC_ALPHA,
+                RL_ONE_MINUS_SRC_ALPHA,
+                RL_ONE,
+                RL_ONE_MINUS_SRC_ALPHA,
+                RL_FUNC_ADD,
+                RL_FUNC_ADD,
+            );
+            ffi::rlSetBlendMode(BLEND_CUSTOM_SEPARATE);
+            ffi::rlScalef(self.scale, self.scale, 1.);
+        }
+
+        self.drawing = true;
+        self.drawn = Some(key);
+    }
+
+    /// Stops drawing into the cache (see [`begin()`](Self::begin())).
+    pub fn end(&mut self, _drawer: &mut impl RaylibDraw) {
+        if !self.drawing {
+            return;
+        }
+
+        // SAFETY: Matches the `BeginTextureMode()` in `begin()`
+        unsafe {
+            ffi::rlSetBlendMode(BLEND_ALPHA);
+            ffi::EndTextureMode();
+        }
+
+        self.drawing = false;
+    }
+
+    /// Returns the scale of what's drawn into the cache, by which e.g.
+    /// scissor regions have to be scaled (see
+    /// [`prepare()`](Self::prepare())).
+    pub const fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Draws the cached grid over the whole window.
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    pub fn draw(&self, drawer: &mut impl RaylibDraw) {
+        let Some(target) = &self.target else {
+            return;
+        };
+        let texture = target.texture();
+
+        // NOTE: Render textures are stored upside down
+        drawer.draw_texture_pro(
+            texture,
+            Rectangle::new(0., 0., texture.width as f32, -(texture.height as f32)),
+            Rectangle::new(0., 0., self.size.0 as f32, self.size.1 as f32),
+            Vector2::zero(),
+            0.,
+            Color::WHITE,
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Recording of the last generations to step back through them.
+
+use std::collections::VecDeque;
+
+/// The last few states of an automaton along with their generations.
+///
+/// Each state is a full copy of the automaton, so the memory taken up
+/// is `length` times that of the automaton.
+pub(crate) struct History<A> {
+    states: VecDeque<(A, u64)>,
+    length: usize,
+    /// The index of the state being shown, if it's not the last one.
+    cursor: Option<usize>,
+    clone: fn(&A) -> A,
+}
+
+impl<A> History<A> {
+    /// Creates an empty history keeping at most `length` states, copied
+    /// with `clone`.
+    pub fn new(length: usize, clone: fn(&A) -> A) -> Self {
+        Self {
+            states: VecDeque::new(),
+            length,
+            cursor: None,
+            clone,
+        }
+    }
+
+    /// Records a new state, dropping the oldest one if the history is
+    /// full.
+    ///
+    /// If an older state is being shown, the states after it are
+    /// dropped, as the automaton has taken a different course.
+    pub fn record(&mut self, automaton: &A, generation: u64) {
+        if self.length == 0 {
+            return;
+        }
+        if let Some(cursor) = self.cursor.take() {
+            self.states.truncate(cursor.saturating_add(1));
+        }
+        if self.states.len() >= self.length {
+            self.states.pop_front();
+        }
+
+        self.states.push_back(((self.clone)(automaton), generation));
+    }
+
+    /// Moves to the previous state, returning a copy of it.
+    pub fn back(&mut self) -> Option<(A, u64)> {
+        let index = self
+            .cursor
+            .unwrap_or_else(|| self.states.len().saturating_sub(1))
+            .checked_sub(1)?;
+        let (automaton, generation) = self.states.get(index)?;
+
+        self.cursor = Some(index);
+
+        Some(((self.clone)(automaton), *generation))
+    }
+
+    /// Moves to the next state, returning a copy of it, or [`None`] if
+    /// the last one is already shown.
+    pub fn forward(&mut self) -> Option<(A, u64)> {
+        let index = self.cursor?.saturating_add(1);
+        let (automaton, generation) = self.states.get(index)?;
+
+        self.cursor = (index.saturating_add(1) < self.states.len()).then_some(index);
+
+        Some(((self.clone)(automaton), *generation))
+    }
+
+    /// Removes all the states.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.cursor = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a history of the given length with the given states
+    /// recorded, each at the generation equal to it.
+    fn history(length: usize, states: &[u64]) -> History<u64> {
+        let mut history = History::new(length, u64::clone);
+
+        for &state in states {
+            history.record(&state, state);
+        }
+
+        history
+    }
+
+    #[test]
+    fn back_and_forward() {
+        let mut history = history(10, &[0, 1, 2, 3]);
+
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some((2, 2)));
+        assert_eq!(history.back(), Some((1, 1)));
+        assert_eq!(history.forward(), Some((2, 2)));
+        assert_eq!(history.forward(), Some((3, 3)));
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn back_to_the_oldest() {
+        let mut history = history(10, &[0, 1]);
+
+        assert_eq!(history.back(), Some((0, 0)));
+        assert_eq!(history.back(), None);
+        assert_eq!(history.forward(), Some((1, 1)));
+    }
+
+    #[test]
+    fn drops_the_oldest() {
+        let mut history = history(2, &[0, 1, 2]);
+
+        assert_eq!(history.back(), Some((1, 1)));
+        assert_eq!(history.back(), None);
+    }
+
+    #[test]
+    fn truncates_on_diverging() {
+        let mut history = history(10, &[0, 1, 2, 3]);
+
+        history.back();
+        history.back();
+        history.record(&10, 2);
+
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some((1, 1)));
+        assert_eq!(history.forward(), Some((10, 2)));
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn zero_length() {
+        let mut history = history(0, &[0, 1]);
+
+        assert!(history.states.is_empty());
+        assert_eq!(history.back(), None);
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut history = history(10, &[0, 1, 2]);
+
+        history.back();
+        history.clear();
+
+        assert_eq!(history.back(), None);
+        assert_eq!(history.forward(), None);
+    }
+}
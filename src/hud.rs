@@ -0,0 +1,1048 @@
+//! Built-in on-screen elements (legend, counters, etc.).
+
+use std::{borrow::Cow, collections::VecDeque, time::Duration};
+
+use raylib::{
+    color::Color,
+    drawing::{RaylibDraw, RaylibDrawHandle},
+    math::{Rectangle, Vector2},
+    text::{measure_text, measure_text_ex, Font},
+};
+
+use crate::{clock::Instant, color::lerp_srgb, timer::frequency_from_period, DetailLevel};
+
+/// Space between the window edges and HUD elements, as well as
+/// between the elements themselves.
+pub(crate) const MARGIN: i32 = 10;
+/// Space between the border of a HUD box and its contents.
+pub(crate) const PADDING: i32 = 6;
+/// Text of the badge shown while paused.
+pub(crate) const DEFAULT_PAUSED_TEXT: &str = "PAUSED";
+/// Text added to the paused badge while paused due to losing focus.
+const UNFOCUSED_SUFFIX: &str = " (unfocused)";
+/// Shown while frames are being exported.
+const RECORDING_TEXT: &str = "REC";
+/// Text placed between the fields of a status bar.
+const DEFAULT_SEPARATOR: &str = " | ";
+/// How long toasts are shown by default.
+pub(crate) const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(2);
+/// Title of the window unless set otherwise.
+pub(crate) const DEFAULT_TITLE: &str = "lifers";
+/// Statistics added to the window title by default.
+pub(crate) const DEFAULT_TITLE_FORMAT: &str = "gen {gen} — pop {pop}";
+/// Text placed between the window title and the statistics.
+const TITLE_SEPARATOR: &str = " — ";
+/// Height of the progress bar towards the generation limit.
+const PROGRESS_HEIGHT: i32 = 4;
+/// Color of the progress bar until the limit is reached.
+const PROGRESS_COLOR: Color = Color::SKYBLUE;
+/// Color of the progress bar once the limit is reached.
+const PROGRESS_DONE_COLOR: Color = Color::LIME;
+/// The default number of toasts shown at once.
+pub(crate) const DEFAULT_MAX_TOASTS: usize = 5;
+/// How long it takes a toast to fade out at the end of its duration.
+const TOAST_FADE: Duration = Duration::from_millis(500);
+
+/// A corner of the window to place HUD elements in.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Corner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    const fn index(self) -> usize {
+        match self {
+            Self::TopLeft => 0,
+            Self::TopRight => 1,
+            Self::BottomLeft => 2,
+            Self::BottomRight => 3,
+        }
+    }
+
+    const fn is_bottom(self) -> bool {
+        matches!(self, Self::BottomLeft | Self::BottomRight)
+    }
+
+    const fn is_right(self) -> bool {
+        matches!(self, Self::TopRight | Self::BottomRight)
+    }
+}
+
+/// Appearance of the HUD elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HudStyle {
+    /// Size of the text.
+    pub font_size: i32,
+    /// Color of the text.
+    pub text_color: Color,
+    /// Color of the boxes behind the text.
+    pub box_color: Color,
+}
+
+impl HudStyle {
+    /// Creates the default style: white text on translucent black.
+    pub const fn new() -> Self {
+        Self {
+            font_size: 20,
+            text_color: Color::RAYWHITE,
+            box_color: Color::new(0, 0, 0, 180),
+        }
+    }
+}
+
+impl Default for HudStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws and measures text with the loaded font, or the default one
+/// of Raylib if there's none.
+#[derive(Clone, Copy)]
+pub(crate) struct TextRenderer<'a> {
+    font: Option<&'a Font>,
+}
+
+impl<'a> TextRenderer<'a> {
+    /// Creates a renderer using the given font.
+    pub const fn new(font: Option<&'a Font>) -> Self {
+        Self { font }
+    }
+
+    /// Returns the spacing between characters for the given font size,
+    /// the same as Raylib uses with its default font.
+    #[allow(clippy::as_conversions)]
+    fn spacing(size: i32) -> f32 {
+        size as f32 / 10.
+    }
+
+    /// Returns the width of the text.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub fn measure(self, text: &str, size: i32) -> i32 {
+        self.font.map_or_else(
+            || measure_text(text, size),
+            |font| measure_text_ex(font, text, size as f32, Self::spacing(size)).x as i32,
+        )
+    }
+
+    /// Draws the text with its top-left corner at the given position.
+    #[allow(clippy::as_conversions)]
+    pub fn draw(
+        self,
+        drawer: &mut impl RaylibDraw,
+        text: &str,
+        x: i32,
+        y: i32,
+        size: i32,
+        color: Color,
+    ) {
+        match self.font {
+            Some(font) => drawer.draw_text_ex(
+                font,
+                text,
+                Vector2::new(x as f32, y as f32),
+                size as f32,
+                Self::spacing(size),
+                color,
+            ),
+            None => drawer.draw_text(text, x, y, size, color),
+        }
+    }
+}
+
+/// Places HUD elements in the window corners, stacking the ones
+/// sharing a corner so they don't overlap.
+pub(crate) struct HudStack<'a> {
+    screen: (i32, i32),
+    offsets: [i32; 4],
+    pub style: HudStyle,
+    pub renderer: TextRenderer<'a>,
+}
+
+impl<'a> HudStack<'a> {
+    /// Creates an empty stack for a window of the given size.
+    pub const fn new(screen: (i32, i32), style: HudStyle, renderer: TextRenderer<'a>) -> Self {
+        Self {
+            screen,
+            offsets: [MARGIN; 4],
+            style,
+            renderer,
+        }
+    }
+
+    /// Keeps the elements out of the strip taken up by the status bar.
+    pub fn reserve(&mut self, bar: &StatusBar) {
+        let corners = match bar.edge {
+            Edge::Top => [Corner::TopLeft, Corner::TopRight],
+            Edge::Bottom => [Corner::BottomLeft, Corner::BottomRight],
+        };
+
+        corners.iter().for_each(|corner| {
+            let offset = &mut self.offsets[corner.index()];
+            *offset = offset.saturating_add(bar.height());
+        });
+    }
+
+    /// Returns the height available to a new element in the given
+    /// corner.
+    pub fn available_height(&self, corner: Corner) -> i32 {
+        self.screen
+            .1
+            .saturating_sub(self.offsets[corner.index()])
+            .saturating_sub(MARGIN)
+    }
+
+    /// Reserves space for an element of the given size in the corner,
+    /// returning its screen position.
+    pub fn place(&mut self, corner: Corner, (width, height): (i32, i32)) -> (i32, i32) {
+        let offset = &mut self.offsets[corner.index()];
+        let x = if corner.is_right() {
+            self.screen.0.saturating_sub(MARGIN).saturating_sub(width)
+        } else {
+            MARGIN
+        };
+        let y = if corner.is_bottom() {
+            self.screen.1.saturating_sub(*offset).saturating_sub(height)
+        } else {
+            *offset
+        };
+
+        *offset = offset.saturating_add(height).saturating_add(MARGIN);
+
+        (x, y)
+    }
+
+    /// Draws a box with a single line of text in the given corner.
+    pub fn draw_text_box(&mut self, drawer: &mut impl RaylibDraw, corner: Corner, text: &str) {
+        self.draw_text_box_with(drawer, corner, text, self.style);
+    }
+
+    /// Like [`draw_text_box()`](Self::draw_text_box()), but with a
+    /// different style.
+    pub fn draw_text_box_with(
+        &mut self,
+        drawer: &mut impl RaylibDraw,
+        corner: Corner,
+        text: &str,
+        style: HudStyle,
+    ) {
+        let HudStyle {
+            font_size,
+            text_color,
+            box_color,
+        } = style;
+        let size = (
+            self.renderer
+                .measure(text, font_size)
+                .saturating_add(PADDING.saturating_mul(2)),
+            font_size.saturating_add(PADDING.saturating_mul(2)),
+        );
+        let (x, y) = self.place(corner, size);
+
+        draw_box(drawer, (x, y), size, box_color);
+        self.renderer.draw(
+            drawer,
+            text,
+            x.saturating_add(PADDING),
+            y.saturating_add(PADDING),
+            font_size,
+            text_color,
+        );
+    }
+
+    /// Draws the background of a HUD element.
+    pub fn draw_box(&self, drawer: &mut impl RaylibDraw, pos: (i32, i32), size: (i32, i32)) {
+        draw_box(drawer, pos, size, self.style.box_color);
+    }
+}
+
+#[allow(clippy::as_conversions)]
+fn draw_box(
+    drawer: &mut impl RaylibDraw,
+    (x, y): (i32, i32),
+    (width, height): (i32, i32),
+    color: Color,
+) {
+    drawer.draw_rectangle_rec(
+        Rectangle::new(x as f32, y as f32, width as f32, height as f32),
+        color,
+    );
+}
+
+/// Formats the time between generations for a toast, in microseconds
+/// below a millisecond, so that fast rates don't show up as `0 ms`.
+pub(crate) fn format_update_rate(update_rate: Duration) -> String {
+    if update_rate < Duration::from_millis(1) {
+        format!("{} µs", update_rate.as_micros())
+    } else {
+        format!("{:.1} ms", update_rate.as_secs_f64() * 1000.)
+    }
+}
+
+/// Builds the window title from the static part and the statistics,
+/// replacing `{gen}`, `{pop}` and `{rate}` in `format`.
+///
+/// An unknown population is shown as `?`.
+pub(crate) fn format_title(
+    title: &str,
+    format: &str,
+    generation: u64,
+    population: Option<usize>,
+    measured_rate: f32,
+) -> String {
+    let stats = format
+        .replace("{gen}", &generation.to_string())
+        .replace(
+            "{pop}",
+            &population.map_or_else(|| "?".to_owned(), |population| population.to_string()),
+        )
+        .replace("{rate}", &format!("{measured_rate:.1}"));
+
+    if title.is_empty() {
+        stats
+    } else {
+        format!("{title}{TITLE_SEPARATOR}{stats}")
+    }
+}
+
+/// Draws a thin bar along the bottom edge of the window, filled
+/// according to the progress towards the generation limit.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+fn draw_progress(
+    drawer: &mut impl RaylibDraw,
+    (width, height): (i32, i32),
+    generation: u64,
+    limit: u64,
+) {
+    let (progress, color) = if generation >= limit {
+        (1., PROGRESS_DONE_COLOR)
+    } else {
+        (generation as f32 / limit as f32, PROGRESS_COLOR)
+    };
+
+    draw_box(
+        drawer,
+        (0, height.saturating_sub(PROGRESS_HEIGHT)),
+        ((width as f32 * progress) as i32, PROGRESS_HEIGHT),
+        color,
+    );
+}
+
+/// Returns the color with its alpha scaled by `opacity`.
+fn faded(color: Color, opacity: f32) -> Color {
+    // NOTE: Only the alpha changes, which is mixed the same way in
+    // either color space
+    lerp_srgb(Color { a: 0, ..color }, color, opacity)
+}
+
+/// A callback drawing a custom HUD each frame.
+///
+/// See [`generic::RaylibFrontend::set_hud()`](crate::generic::RaylibFrontend::set_hud()).
+pub type HudCallback = Box<dyn FnMut(&mut RaylibDrawHandle<'_>, &HudContext)>;
+
+/// State of the frontend in the current frame, used to draw the HUD.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub struct HudContext {
+    /// The number of generations computed so far.
+    pub generation: u64,
+    /// The number of live cells, if known.
+    pub population: Option<usize>,
+    /// Whether the simulation is paused.
+    pub paused: bool,
+    /// Whether the simulation is paused only because the window lost
+    /// focus.
+    pub focus_paused: bool,
+    /// Frames per second.
+    pub fps: u32,
+    /// Duration of the last frame in seconds.
+    pub frame_time: f32,
+    /// The configured time between generations.
+    pub update_rate: Duration,
+    /// Generations per second actually computed recently.
+    pub measured_rate: f32,
+    /// The generation the simulation pauses at, if set.
+    pub max_generations: Option<u64>,
+    /// Time taken by steps and drawing.
+    pub frame_stats: FrameStats,
+    /// Whether generations are computed as fast as possible.
+    pub turbo: bool,
+    /// Whether frames are being exported.
+    pub recording: bool,
+    /// The generation of the last successful autosave, if any.
+    pub last_autosave: Option<u64>,
+    /// Births and deaths in the last step, if tracked.
+    pub step_stats: Option<StepStats>,
+    /// Size of the window.
+    pub window_size: (i32, i32),
+    /// Screen area taken up by the grid.
+    pub grid_rect: Rectangle,
+    /// How much of the grid is drawn at the current cell size.
+    pub detail: DetailLevel,
+}
+
+/// Smoothed timings of the simulation and the rendering.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    /// Average time taken by a single step of the automaton.
+    pub step_time: Duration,
+    /// Average time taken by drawing a frame, not counting waiting for
+    /// the next one.
+    pub draw_time: Duration,
+    /// The number of cells drawn in the last frame.
+    pub cells_drawn: usize,
+    /// The number of bytes of the grid texture uploaded to the GPU in
+    /// the last frame, in pixel mode.
+    pub bytes_uploaded: usize,
+    /// The number of heap allocations made by drawing the grid in the
+    /// last frame, only counted with the `count-allocations` feature
+    /// (see the `alloc_count` module).
+    pub grid_allocations: usize,
+}
+
+/// Keeps exponentially smoothed averages of the step and draw times.
+pub(crate) struct FrameTimer {
+    step_time: f32,
+    draw_time: f32,
+    cells_drawn: usize,
+    bytes_uploaded: usize,
+    grid_allocations: usize,
+}
+
+impl FrameTimer {
+    /// How much a new measurement affects the average.
+    const SMOOTHING: f32 = 0.1;
+
+    /// Creates a timer with no measurements.
+    pub const fn new() -> Self {
+        Self {
+            step_time: 0.,
+            draw_time: 0.,
+            cells_drawn: 0,
+            bytes_uploaded: 0,
+            grid_allocations: 0,
+        }
+    }
+
+    fn smooth(average: f32, time: Duration) -> f32 {
+        (time.as_secs_f32() - average).mul_add(Self::SMOOTHING, average)
+    }
+
+    /// Records the time taken by a step.
+    pub fn record_step(&mut self, time: Duration) {
+        self.step_time = Self::smooth(self.step_time, time);
+    }
+
+    /// Records the time taken by drawing a frame, along with how much
+    /// it drew.
+    pub fn record_draw(&mut self, time: Duration, cells_drawn: usize, bytes_uploaded: usize) {
+        self.draw_time = Self::smooth(self.draw_time, time);
+        self.cells_drawn = cells_drawn;
+        self.bytes_uploaded = bytes_uploaded;
+    }
+
+    /// Records the number of allocations made by drawing the grid in
+    /// the last frame.
+    #[cfg(feature = "count-allocations")]
+    pub fn record_allocations(&mut self, allocations: usize) {
+        self.grid_allocations = allocations;
+    }
+
+    /// Returns the current averages.
+    pub fn stats(&self) -> FrameStats {
+        FrameStats {
+            step_time: Duration::from_secs_f32(self.step_time.max(0.)),
+            draw_time: Duration::from_secs_f32(self.draw_time.max(0.)),
+            cells_drawn: self.cells_drawn,
+            bytes_uploaded: self.bytes_uploaded,
+            grid_allocations: self.grid_allocations,
+        }
+    }
+}
+
+/// The number of cells that changed state in a single step.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepStats {
+    /// The number of cells that became alive.
+    pub births: usize,
+    /// The number of cells that died.
+    pub deaths: usize,
+}
+
+/// A statistic shown by the graph.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphSeries {
+    /// The number of live cells.
+    #[default]
+    Population,
+    /// The number of cells born in each step.
+    Births,
+    /// The number of cells that died in each step.
+    Deaths,
+}
+
+impl GraphSeries {
+    /// Returns the value of the statistic, if it's known.
+    #[allow(clippy::as_conversions)]
+    pub(crate) fn sample(
+        self,
+        population: Option<usize>,
+        step_stats: Option<StepStats>,
+    ) -> Option<f32> {
+        match self {
+            Self::Population => population,
+            Self::Births => step_stats.map(|stats| stats.births),
+            Self::Deaths => step_stats.map(|stats| stats.deaths),
+        }
+        .map(|value| value as f32)
+    }
+
+    /// Checks whether births and deaths have to be tracked for this
+    /// statistic.
+    pub(crate) const fn needs_step_stats(self) -> bool {
+        matches!(self, Self::Births | Self::Deaths)
+    }
+}
+
+/// Which built-in HUD elements are shown, and where.
+pub(crate) struct Hud {
+    pub style: HudStyle,
+    pub corner: Corner,
+    pub show_generation: bool,
+    pub show_fps: bool,
+    pub show_population: bool,
+    pub show_rate: bool,
+    pub rate_unit: RateUnit,
+    /// Text of the badge shown while paused, if enabled.
+    pub paused_text: Option<Cow<'static, str>>,
+    /// Whether the progress towards the generation limit is shown.
+    pub show_progress: bool,
+    pub show_timings: bool,
+}
+
+impl Hud {
+    /// Creates a HUD with all the elements but the paused badge hidden.
+    pub const fn new() -> Self {
+        Self {
+            style: HudStyle::new(),
+            corner: Corner::TopLeft,
+            show_generation: false,
+            show_fps: false,
+            show_population: false,
+            show_rate: false,
+            rate_unit: RateUnit::MillisPerStep,
+            paused_text: Some(Cow::Borrowed(DEFAULT_PAUSED_TEXT)),
+            show_progress: true,
+            show_timings: false,
+        }
+    }
+
+    /// Draws the enabled elements.
+    pub fn draw(&self, drawer: &mut impl RaylibDraw, stack: &mut HudStack, stats: &HudContext) {
+        if let (true, Some(limit)) = (self.show_progress, stats.max_generations) {
+            draw_progress(drawer, stats.window_size, stats.generation, limit);
+        }
+        match (stats.paused, &self.paused_text) {
+            (true, Some(text)) if stats.focus_paused => {
+                stack.draw_text_box(drawer, self.corner, &format!("{text}{UNFOCUSED_SUFFIX}"));
+            }
+            (true, Some(text)) => stack.draw_text_box(drawer, self.corner, text),
+            _ => (),
+        }
+        if stats.turbo {
+            stack.draw_text_box(
+                drawer,
+                self.corner,
+                &format!("TURBO: {:.0} gen/s", stats.measured_rate),
+            );
+        }
+        if stats.recording {
+            stack.draw_text_box(drawer, self.corner, RECORDING_TEXT);
+        }
+        [
+            (self.show_generation, StatusField::Generation),
+            (self.show_population, StatusField::Population),
+            (
+                self.show_rate,
+                match self.rate_unit {
+                    RateUnit::MillisPerStep => StatusField::Rate,
+                    RateUnit::StepsPerSecond => StatusField::StepsPerSecond,
+                },
+            ),
+            (self.show_fps, StatusField::Fps),
+        ]
+        .iter()
+        .filter(|(shown, _)| *shown)
+        .filter_map(|(_, field)| field.text(stats))
+        .for_each(|text| stack.draw_text_box(drawer, self.corner, &text));
+        if self.show_timings {
+            let FrameStats {
+                step_time,
+                draw_time,
+                cells_drawn,
+                bytes_uploaded,
+                ..
+            } = stats.frame_stats;
+
+            stack.draw_text_box(
+                drawer,
+                self.corner,
+                &format!(
+                    "step: {:.2} ms, draw: {:.2} ms, cells drawn: {cells_drawn}, \
+                     uploaded: {bytes_uploaded} B, detail: {}",
+                    step_time.as_secs_f32() * 1000.,
+                    draw_time.as_secs_f32() * 1000.,
+                    stats.detail,
+                ),
+            );
+        }
+    }
+}
+
+/// A piece of information shown in a [`StatusBar`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StatusField {
+    /// The number of generations computed so far.
+    Generation,
+    /// The number of live cells (skipped if unknown).
+    Population,
+    /// The configured and the measured update rate.
+    Rate,
+    /// The configured and the measured update rate, in generations
+    /// per second.
+    StepsPerSecond,
+    /// Frames per second along with the frame time.
+    Fps,
+    /// A badge shown only while the simulation is paused.
+    Paused,
+    /// The number of cells born in the last step (skipped if not
+    /// tracked).
+    Births,
+    /// The number of cells that died in the last step (skipped if not
+    /// tracked).
+    Deaths,
+    /// The generation of the last successful autosave (skipped if
+    /// there was none).
+    LastAutosave,
+    /// Arbitrary text.
+    Custom(String),
+}
+
+impl StatusField {
+    /// Returns the text of the field in the given frame, or [`None`] if
+    /// there's nothing to show.
+    pub fn text(&self, context: &HudContext) -> Option<Cow<'_, str>> {
+        match self {
+            Self::Generation => Some(format!("Generation: {}", context.generation).into()),
+            Self::Population => context
+                .population
+                .map(|population| format!("Population: {population}").into()),
+            Self::Rate => Some(
+                format!(
+                    "{:.0} ms/gen ({:.1} gen/s)",
+                    context.update_rate.as_secs_f32() * 1000.,
+                    context.measured_rate
+                )
+                .into(),
+            ),
+            Self::StepsPerSecond => Some(
+                format!(
+                    "{:.1} gen/s ({:.1} gen/s measured)",
+                    frequency_from_period(context.update_rate),
+                    context.measured_rate
+                )
+                .into(),
+            ),
+            Self::Fps => Some(
+                format!(
+                    "FPS: {} ({:.1} ms)",
+                    context.fps,
+                    context.frame_time * 1000.
+                )
+                .into(),
+            ),
+            Self::Paused => context.paused.then_some(Cow::Borrowed(DEFAULT_PAUSED_TEXT)),
+            Self::Births => context
+                .step_stats
+                .map(|stats| format!("Births: {}", stats.births).into()),
+            Self::Deaths => context
+                .step_stats
+                .map(|stats| format!("Deaths: {}", stats.deaths).into()),
+            Self::LastAutosave => context
+                .last_autosave
+                .map(|generation| format!("Autosaved: {generation}").into()),
+            Self::Custom(text) => Some(Cow::Borrowed(text)),
+        }
+    }
+}
+
+/// The unit the update rate is shown in.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateUnit {
+    /// Milliseconds per generation.
+    #[default]
+    MillisPerStep,
+    /// Generations per second.
+    StepsPerSecond,
+}
+
+/// An edge of the window.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Edge {
+    #[default]
+    Top,
+    Bottom,
+}
+
+/// A single line of [`StatusField`]s along the top or bottom edge of
+/// the window.
+///
+/// The strip it takes up is reserved, so the grid is laid out in the
+/// rest of the window instead of being covered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusBar {
+    /// The fields, in the order they're shown.
+    pub fields: Vec<StatusField>,
+    /// The edge of the window the bar is placed along.
+    pub edge: Edge,
+    /// Text placed between the fields.
+    pub separator: Cow<'static, str>,
+    /// Font size and colors of the bar.
+    pub style: HudStyle,
+}
+
+impl StatusBar {
+    /// Creates a bar along the top edge showing the given fields.
+    pub fn new(fields: impl IntoIterator<Item = StatusField>) -> Self {
+        Self {
+            fields: fields.into_iter().collect(),
+            edge: Edge::Top,
+            separator: Cow::Borrowed(DEFAULT_SEPARATOR),
+            style: HudStyle::new(),
+        }
+    }
+
+    /// Sets the edge of the window the bar is placed along.
+    #[must_use]
+    pub fn edge(self, edge: Edge) -> Self {
+        Self { edge, ..self }
+    }
+
+    /// Sets the text placed between the fields (`" | "` by default).
+    #[must_use]
+    pub fn separator(self, separator: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            separator: separator.into(),
+            ..self
+        }
+    }
+
+    /// Sets the font size and colors of the bar.
+    #[must_use]
+    pub fn style(self, style: HudStyle) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Checks whether births and deaths have to be tracked for the
+    /// fields.
+    pub(crate) fn needs_step_stats(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field, StatusField::Births | StatusField::Deaths))
+    }
+
+    /// Returns the height of the strip taken up by the bar.
+    pub(crate) fn height(&self) -> i32 {
+        self.style
+            .font_size
+            .saturating_add(PADDING.saturating_mul(2))
+    }
+
+    /// Splits the window into the strip taken up by the bar and the
+    /// area left for the grid.
+    #[allow(clippy::as_conversions)]
+    pub(crate) fn split(&self, window: Rectangle) -> (Rectangle, Rectangle) {
+        let height = (self.height() as f32).min(window.height);
+        let rest = window.height - height;
+
+        match self.edge {
+            Edge::Top => (
+                Rectangle::new(window.x, window.y, window.width, height),
+                Rectangle::new(window.x, window.y + height, window.width, rest),
+            ),
+            Edge::Bottom => (
+                Rectangle::new(window.x, window.y + rest, window.width, height),
+                Rectangle::new(window.x, window.y, window.width, rest),
+            ),
+        }
+    }
+
+    /// Draws the bar with the fields that have something to show.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub(crate) fn draw(
+        &self,
+        drawer: &mut impl RaylibDraw,
+        renderer: TextRenderer<'_>,
+        context: &HudContext,
+    ) {
+        let (window_width, window_height) = context.window_size;
+        let (rect, _) = self.split(Rectangle::new(
+            0.,
+            0.,
+            window_width as f32,
+            window_height as f32,
+        ));
+        let text = self
+            .fields
+            .iter()
+            .filter_map(|field| field.text(context))
+            .collect::<Vec<_>>()
+            .join(&*self.separator);
+
+        drawer.draw_rectangle_rec(rect, self.style.box_color);
+        renderer.draw(
+            drawer,
+            &text,
+            PADDING,
+            (rect.y as i32).saturating_add(PADDING),
+            self.style.font_size,
+            self.style.text_color,
+        );
+    }
+}
+
+/// Measures how many generations are computed per second, averaged
+/// over (at least) a second.
+pub(crate) struct RateMeter {
+    window_start: Instant,
+    steps: u32,
+    rate: f32,
+}
+
+impl RateMeter {
+    /// The time over which the steps are averaged.
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    /// Creates a meter with no recorded steps.
+    pub fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            steps: 0,
+            rate: 0.,
+        }
+    }
+
+    /// Records a single step.
+    pub fn record(&mut self) {
+        self.steps = self.steps.saturating_add(1);
+    }
+
+    /// Returns the rate measured over the last full window.
+    #[allow(clippy::as_conversions)]
+    pub fn rate(&mut self) -> f32 {
+        let elapsed = self.window_start.elapsed();
+
+        if elapsed >= Self::WINDOW {
+            self.rate = self.steps as f32 / elapsed.as_secs_f32();
+            self.steps = 0;
+            self.window_start = Instant::now();
+        }
+
+        self.rate
+    }
+}
+
+/// Short messages shown for a while, e.g. as feedback to key
+/// presses.
+pub(crate) struct Toasts {
+    /// Texts along with the time they expire at and their duration.
+    queue: VecDeque<(Cow<'static, str>, Instant, Duration)>,
+    /// The number of toasts shown at once, older ones are dropped.
+    pub max: usize,
+    pub corner: Corner,
+    pub style: HudStyle,
+}
+
+impl Toasts {
+    /// Creates an empty queue.
+    pub const fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            max: DEFAULT_MAX_TOASTS,
+            corner: Corner::BottomRight,
+            style: HudStyle::new(),
+        }
+    }
+
+    /// Queues a message shown for the given duration, dropping the
+    /// oldest ones if there are too many.
+    pub fn push(&mut self, text: Cow<'static, str>, duration: Duration) {
+        while self.queue.len() >= self.max.max(1) {
+            self.queue.pop_front();
+        }
+
+        let now = Instant::now();
+
+        self.queue
+            .push_back((text, now.checked_add(duration).unwrap_or(now), duration));
+    }
+
+    /// Returns the texts of the active toasts, oldest first.
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        let now = Instant::now();
+
+        self.queue
+            .iter()
+            .filter(move |(_, expires, _)| *expires > now)
+            .map(|(text, _, _)| text.as_ref())
+    }
+
+    /// Draws the active toasts, fading them out at the end, and drops
+    /// the expired ones.
+    pub fn draw(&mut self, drawer: &mut impl RaylibDraw, stack: &mut HudStack) {
+        let now = Instant::now();
+        self.queue.retain(|(_, expires, _)| *expires > now);
+
+        self.queue.iter().for_each(|(text, expires, duration)| {
+            let fade = TOAST_FADE.min(*duration).as_secs_f32();
+            let opacity = if fade > 0. {
+                expires.saturating_duration_since(now).as_secs_f32() / fade
+            } else {
+                1.
+            };
+            let style = HudStyle {
+                text_color: faded(self.style.text_color, opacity),
+                box_color: faded(self.style.box_color, opacity),
+                ..self.style
+            };
+
+            stack.draw_text_box_with(drawer, self.corner, text, style);
+        });
+    }
+}
+
+/// A legend explaining the meaning of cell colors.
+pub(crate) struct Legend {
+    /// Labels and the colors they describe.
+    pub entries: Vec<(String, Color)>,
+    pub corner: Corner,
+    pub visible: bool,
+}
+
+impl Legend {
+    /// Creates a legend with no entries.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            corner: Corner::TopRight,
+            visible: true,
+        }
+    }
+
+    /// Draws the legend, wrapping the entries into several columns if
+    /// they don't fit in the window vertically.
+    pub fn draw(&self, drawer: &mut impl RaylibDraw, stack: &mut HudStack) {
+        if !self.visible || self.entries.is_empty() {
+            return;
+        }
+
+        let HudStyle {
+            font_size,
+            text_color,
+            ..
+        } = stack.style;
+        let line_height = font_size.saturating_add(PADDING);
+        let rows_fit = stack
+            .available_height(self.corner)
+            .saturating_sub(PADDING)
+            .checked_div(line_height)
+            .unwrap_or(0);
+        let rows_n = usize::try_from(rows_fit)
+            .unwrap_or(0)
+            .clamp(1, self.entries.len());
+        let column_widths: Vec<_> = self
+            .entries
+            .chunks(rows_n)
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|(label, _)| stack.renderer.measure(label, font_size))
+                    .max()
+                    .unwrap_or(0)
+                    .saturating_add(font_size)
+                    .saturating_add(PADDING)
+            })
+            .collect();
+        let rows = i32::try_from(rows_n).unwrap_or(i32::MAX);
+        let size = (
+            column_widths.iter().fold(PADDING, |width, column| {
+                width.saturating_add(*column).saturating_add(PADDING)
+            }),
+            rows.saturating_mul(line_height).saturating_add(PADDING),
+        );
+        let (x, y) = stack.place(self.corner, size);
+
+        stack.draw_box(drawer, (x, y), size);
+
+        let renderer = stack.renderer;
+        let mut column_x = x.saturating_add(PADDING);
+
+        self.entries
+            .chunks(rows_n)
+            .zip(&column_widths)
+            .for_each(|(column, width)| {
+                let mut row_y = y.saturating_add(PADDING);
+
+                column.iter().for_each(|(label, color)| {
+                    draw_swatch(drawer, (column_x, row_y), font_size, *color);
+                    renderer.draw(
+                        drawer,
+                        label,
+                        column_x.saturating_add(font_size).saturating_add(PADDING),
+                        row_y,
+                        font_size,
+                        text_color,
+                    );
+
+                    row_y = row_y.saturating_add(line_height);
+                });
+
+                column_x = column_x.saturating_add(*width).saturating_add(PADDING);
+            });
+    }
+}
+
+#[allow(clippy::as_conversions)]
+fn draw_swatch(drawer: &mut impl RaylibDraw, (x, y): (i32, i32), size: i32, color: Color) {
+    let size = size as f32;
+
+    drawer.draw_rectangle_v(
+        Vector2::new(x as f32, y as f32),
+        Vector2::new(size, size),
+        color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_rate() {
+        assert_eq!(format_update_rate(Duration::from_millis(100)), "100.0 ms");
+        assert_eq!(format_update_rate(Duration::from_micros(2_500)), "2.5 ms");
+        assert_eq!(format_update_rate(Duration::from_micros(250)), "250 µs");
+        assert_eq!(format_update_rate(Duration::ZERO), "0 µs");
+    }
+}
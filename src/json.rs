@@ -0,0 +1,99 @@
+//! Export of the cells as JSON, e.g. to plot them with other tools
+//! (see [`generic::RaylibFrontend::export_json()`](crate::generic::RaylibFrontend::export_json())).
+//!
+//! The document is an object with the `width` and `height` of the grid
+//! and the selected `states` and `data` of the cells.  Generic automata
+//! write them as arrays of rows, life-like ones as lists of
+//! `{"x": .., "y": .., "value": ..}` records of the live cells.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Serialize, Serializer};
+
+/// The parts of the cells exported.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportSelection {
+    /// The states of the cells.
+    #[default]
+    States,
+    /// The data computed for the cells by the automaton.
+    Data,
+    /// Both the states and the data.
+    Both,
+}
+
+impl ExportSelection {
+    /// Checks whether the states are exported.
+    pub const fn states(self) -> bool {
+        matches!(self, Self::States | Self::Both)
+    }
+
+    /// Checks whether the data is exported.
+    pub const fn data(self) -> bool {
+        matches!(self, Self::Data | Self::Both)
+    }
+}
+
+#[derive(Serialize)]
+struct Document<T, U> {
+    width: usize,
+    height: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    states: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<U>,
+}
+
+#[derive(Serialize)]
+struct Record<'a, V> {
+    x: usize,
+    y: usize,
+    value: &'a V,
+}
+
+/// The cells of a sparse grid, serialized as a list of records while
+/// they're iterated over.
+pub(crate) struct Records<'a, M>(pub &'a M);
+
+impl<'a, M, V> Serialize for Records<'a, M>
+where
+    &'a M: IntoIterator<Item = (&'a (usize, usize), &'a V)>,
+    V: Serialize + 'a,
+{
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.collect_seq(
+            self.0
+                .into_iter()
+                .map(|(&(x, y), value)| Record { x, y, value }),
+        )
+    }
+}
+
+/// Writes the selected parts of the cells to a file, streaming them
+/// instead of building the whole document first.
+pub(crate) fn write<T: Serialize, U: Serialize>(
+    path: &Path,
+    (width, height): (usize, usize),
+    what: ExportSelection,
+    states: T,
+    data: U,
+) -> serde_json::Result<()> {
+    let mut writer = BufWriter::new(File::create(path).map_err(serde_json::Error::io)?);
+
+    serde_json::to_writer(
+        &mut writer,
+        &Document {
+            width,
+            height,
+            states: what.states().then_some(states),
+            data: what.data().then_some(data),
+        },
+    )?;
+
+    writer.flush().map_err(serde_json::Error::io)
+}
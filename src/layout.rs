@@ -0,0 +1,404 @@
+//! Helpers for laying out the grid in the window.
+//!
+//! The frontends lay out the grid with [`lay_out()`] from a
+//! [`LayoutInputs`], and only do it again when any of the inputs
+//! changed, at the start of the next frame.
+
+use raylib::{
+    camera::Camera2D,
+    math::{Rectangle, Vector2},
+};
+
+use crate::{map_vecs, DetailLevel};
+
+/// How far (in pixels) the cells may fall short of the window edges
+/// while still being considered to cover it.
+const COVERAGE_TOLERANCE: f32 = 0.5;
+/// The sides of the cells (in pixels) below which the margins are
+/// dropped, and below which the grid is drawn with a pixel per cell
+/// (see [`DetailLevel`](crate::DetailLevel)).
+pub const DEFAULT_DETAIL_THRESHOLDS: (f32, f32) = (3., 1.5);
+
+/// Everything the layout of the grid depends on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutInputs {
+    /// The size of the window, in logical units (see
+    /// [`RaylibFrontend::dpi_scale()`](crate::generic::RaylibFrontend::dpi_scale())).
+    pub window_size: (i32, i32),
+    /// The part of the window the grid is fitted into, e.g. without
+    /// the status bar.
+    pub area: Rectangle,
+    /// The number of columns and rows of the grid.
+    pub grid_size: (usize, usize),
+    /// The number of cells laid out around the grid on every side (the
+    /// wrap preview band), which have to fit as well.
+    pub band: usize,
+    /// The margin between the cells, when they're big enough for it.
+    pub margin: u32,
+    /// See [`DEFAULT_DETAIL_THRESHOLDS`].
+    pub detail_thresholds: (f32, f32),
+    /// Whether the grid takes up the whole area, starting at its
+    /// top-left corner, rather than being centered in it.
+    pub fill_area: bool,
+}
+
+/// The grid as laid out in the window (see [`lay_out()`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Layout {
+    /// The size of the window it was laid out for.
+    pub window_size: (i32, i32),
+    /// The sides the cells would have with the margins and without
+    /// them.
+    pub fitted_cell_sizes: (f32, f32),
+    /// How much of the grid is drawn at this cell size.
+    pub detail: DetailLevel,
+    /// The margin between the cells, zero if they're too small for it.
+    pub cell_margin: u32,
+    /// The side of the cells.
+    pub cell_size: f32,
+    /// The top-left corner of the grid, before the margin of the first
+    /// cell.
+    pub origin: Vector2,
+    /// The part of the window the grid is drawn in.
+    pub grid_rect: Rectangle,
+    /// Whether the cells leave no part of the window visible (see
+    /// [`covers_window()`]).
+    pub covers_window: bool,
+}
+
+impl Layout {
+    /// Checks whether the background has to be cleared before drawing
+    /// the cells, given whether they're drawn `to_texture` (which keeps
+    /// the previous frame) and whether clearing is forced.
+    ///
+    /// Nothing but the cells is visible if they cover the whole window.
+    pub const fn needs_clear(&self, to_texture: bool, always_clear: bool) -> bool {
+        to_texture || always_clear || !self.covers_window
+    }
+}
+
+/// Fits the grid (along with the band around it) into the area, as
+/// large as the area allows with square cells, and centers it there.
+///
+/// Grids without cells and areas too small for the margins are laid
+/// out with empty cells instead of infinite or negative ones.
+#[allow(clippy::as_conversions)]
+pub fn lay_out(inputs: &LayoutInputs) -> Layout {
+    let LayoutInputs {
+        window_size,
+        area,
+        grid_size,
+        band,
+        margin,
+        detail_thresholds,
+        fill_area,
+    } = *inputs;
+    let area_size = Vector2::new(area.width.max(0.), area.height.max(0.));
+    let band = band as f32;
+    let grid_dimensions = Vector2::new(grid_size.0 as f32, grid_size.1 as f32);
+    let layout_dimensions = map_vecs!(
+        grid_dimensions
+        => |cells: f32| 2f32.mul_add(band, cells)
+    );
+    let fit = |margin: f32| {
+        let Vector2 { x, y } = map_vecs!(
+            area_size,
+            layout_dimensions
+            => |side, cells: f32| (cells + 1.).mul_add(-margin, side) / cells.max(1.)
+        );
+
+        x.min(y).max(0.)
+    };
+    let fitted_cell_sizes = (fit(margin as f32), fit(0.));
+    // NOTE: The margins are dropped if the cells would be too small
+    // with them
+    let detail = DetailLevel::for_cell_size(fitted_cell_sizes, detail_thresholds);
+    let cell_margin = match detail {
+        DetailLevel::Full => margin,
+        _ => 0,
+    };
+    let margin = cell_margin as f32;
+    let cell_size = fit(margin);
+    let [grid_extent, layout_extent] = [grid_dimensions, layout_dimensions].map(|dimensions| {
+        map_vecs!(dimensions => |cells: f32| cells.mul_add(cell_size, (cells + 1.) * margin))
+    });
+    let layout_rect = Rectangle::new(
+        (area_size.x - layout_extent.x).mul_add(0.5, area.x),
+        (area_size.y - layout_extent.y).mul_add(0.5, area.y),
+        layout_extent.x,
+        layout_extent.y,
+    );
+
+    // NOTE: When filling the area, the cells that don't fit entirely
+    // are clipped
+    let (origin, grid_rect, cells_rect) = if fill_area {
+        (Vector2::new(area.x, area.y), area, area)
+    } else {
+        let origin = map_vecs!(
+            Vector2::new(layout_rect.x, layout_rect.y)
+            => |pos: f32| band.mul_add(cell_size + margin, pos)
+        );
+
+        (
+            origin,
+            Rectangle::new(origin.x, origin.y, grid_extent.x, grid_extent.y),
+            layout_rect,
+        )
+    };
+    let window = Vector2::new(window_size.0 as f32, window_size.1 as f32);
+
+    Layout {
+        window_size,
+        fitted_cell_sizes,
+        detail,
+        cell_margin,
+        cell_size,
+        origin,
+        grid_rect,
+        covers_window: covers_window(cells_rect, window, cell_margin),
+    }
+}
+
+/// Checks whether the cells drawn over `cells_rect` leave no part of
+/// the window visible, in which case clearing the background can be
+/// skipped.
+///
+/// Any margin between the cells makes the background visible.
+pub fn covers_window(cells_rect: Rectangle, window_size: Vector2, cell_margin: u32) -> bool {
+    cell_margin == 0
+        && cells_rect.x <= COVERAGE_TOLERANCE
+        && cells_rect.y <= COVERAGE_TOLERANCE
+        && cells_rect.x + cells_rect.width >= window_size.x - COVERAGE_TOLERANCE
+        && cells_rect.y + cells_rect.height >= window_size.y - COVERAGE_TOLERANCE
+}
+
+/// Returns a camera that scales an area of the window to fit an image
+/// of the given size, keeping its aspect ratio and centering it (so
+/// that the rest of the image is left empty).
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub fn fit_camera(image_size: (u32, u32), area: Rectangle) -> Camera2D {
+    let image_size = Vector2::new(image_size.0 as f32, image_size.1 as f32);
+    let area_size = Vector2::new(area.width.max(1.), area.height.max(1.));
+    let zoom = (image_size.x / area_size.x).min(image_size.y / area_size.y);
+
+    Camera2D {
+        offset: Vector2::new(
+            area_size.x.mul_add(-zoom, image_size.x) / 2.,
+            area_size.y.mul_add(-zoom, image_size.y) / 2.,
+        ),
+        target: Vector2::new(area.x, area.y),
+        rotation: 0.,
+        zoom,
+    }
+}
+
+/// Returns the area taken up by a window of the given size.
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub fn window_rect(window_size: (i32, i32)) -> Rectangle {
+    Rectangle::new(0., 0., window_size.0 as f32, window_size.1 as f32)
+}
+
+/// Returns the scissor region (x, y, width and height in pixels)
+/// covering the given area, rounded outwards so that no partially
+/// covered pixel is clipped.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+pub fn scissor_rect(area: Rectangle) -> (i32, i32, i32, i32) {
+    let (left, top) = (area.x.floor(), area.y.floor());
+    let (right, bottom) = ((area.x + area.width).ceil(), (area.y + area.height).ceil());
+
+    (
+        left as i32,
+        top as i32,
+        (right - left) as i32,
+        (bottom - top) as i32,
+    )
+}
+
+/// Returns the coordinates of the cells along an axis, from `-band` to
+/// `cells + band` (exclusive), with cells of the given size and the
+/// margin between them, starting at `offset`.
+#[allow(clippy::as_conversions)]
+pub fn cell_coordinates(
+    cells: usize,
+    band: usize,
+    size: f32,
+    margin: f32,
+    offset: f32,
+) -> Vec<f32> {
+    let (cells, band) = (cells as isize, band as isize);
+
+    (band.saturating_neg()..cells.saturating_add(band))
+        .map(|pos| {
+            let pos = pos as f32;
+
+            pos.mul_add(size, (pos + 1.) * margin) + offset
+        })
+        .collect()
+}
+
+// NOTE: The sizes compared are exact, and the literals in tuples are
+// flagged even with their types known
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::default_numeric_fallback)]
+mod tests {
+    use super::*;
+
+    /// Returns the inputs for a grid fitted into the whole window.
+    fn inputs(window_size: (i32, i32), grid_size: (usize, usize), margin: u32) -> LayoutInputs {
+        LayoutInputs {
+            window_size,
+            area: window_rect(window_size),
+            grid_size,
+            band: 0,
+            margin,
+            detail_thresholds: DEFAULT_DETAIL_THRESHOLDS,
+            fill_area: false,
+        }
+    }
+
+    #[test]
+    fn non_square_grid() {
+        let layout = lay_out(&inputs((800, 600), (40, 20), 0));
+
+        assert_eq!(layout.cell_size, 20.);
+        assert_eq!(layout.origin, Vector2::new(0., 100.));
+        assert_eq!(layout.grid_rect, Rectangle::new(0., 100., 800., 400.));
+        assert!(!layout.covers_window);
+    }
+
+    #[test]
+    fn band() {
+        let layout = lay_out(&LayoutInputs {
+            band: 5,
+            ..inputs((200, 200), (10, 10), 0)
+        });
+
+        assert_eq!(layout.cell_size, 10.);
+        assert_eq!(layout.origin, Vector2::new(50., 50.));
+        assert_eq!(layout.grid_rect, Rectangle::new(50., 50., 100., 100.));
+        assert!(layout.covers_window);
+    }
+
+    #[test]
+    fn covers_window_when_fitting_exactly() {
+        assert!(lay_out(&inputs((100, 100), (10, 10), 0)).covers_window);
+        assert!(!lay_out(&inputs((100, 100), (10, 10), 1)).covers_window);
+        assert!(!lay_out(&inputs((200, 100), (10, 10), 0)).covers_window);
+        assert!(
+            lay_out(&LayoutInputs {
+                fill_area: true,
+                ..inputs((200, 100), (10, 10), 0)
+            })
+            .covers_window
+        );
+    }
+
+    #[test]
+    fn covers_window_decision() {
+        let window = Vector2::new(100., 80.);
+
+        assert!(covers_window(Rectangle::new(0., 0., 100., 80.), window, 0));
+        assert!(covers_window(
+            Rectangle::new(-20., -10., 140., 100.),
+            window,
+            0
+        ));
+        // NOTE: Within the tolerance of rounding
+        assert!(covers_window(
+            Rectangle::new(0.4, 0.4, 99.3, 79.3),
+            window,
+            0
+        ));
+        assert!(!covers_window(Rectangle::new(0., 0., 100., 80.), window, 1));
+        assert!(!covers_window(Rectangle::new(1., 0., 99., 80.), window, 0));
+        assert!(!covers_window(Rectangle::new(0., 1., 100., 79.), window, 0));
+        assert!(!covers_window(Rectangle::new(0., 0., 99., 80.), window, 0));
+        assert!(!covers_window(Rectangle::new(0., 0., 100., 79.), window, 0));
+    }
+
+    #[test]
+    fn needs_clear() {
+        let covering = lay_out(&inputs((100, 100), (10, 10), 0));
+        let with_margins = lay_out(&inputs((100, 100), (10, 10), 1));
+
+        assert!(!covering.needs_clear(false, false));
+        assert!(covering.needs_clear(true, false));
+        assert!(covering.needs_clear(false, true));
+        assert!(with_margins.needs_clear(false, false));
+        assert!(with_margins.needs_clear(true, true));
+    }
+
+    #[test]
+    fn margins() {
+        let layout = lay_out(&inputs((100, 100), (10, 10), 3));
+
+        assert_eq!(layout.detail, DetailLevel::Full);
+        assert_eq!(layout.cell_margin, 3);
+        assert!((layout.cell_size - 6.7).abs() < 1e-4);
+    }
+
+    #[test]
+    fn margins_larger_than_cells() {
+        let layout = lay_out(&inputs((100, 100), (50, 50), 10));
+
+        assert_eq!(layout.fitted_cell_sizes, (0., 2.));
+        assert_eq!(layout.detail, DetailLevel::NoMargins);
+        assert_eq!(layout.cell_margin, 0);
+        assert_eq!(layout.cell_size, 2.);
+        assert!(layout.covers_window);
+
+        let layout = lay_out(&inputs((100, 100), (200, 200), 10));
+
+        assert_eq!(layout.detail, DetailLevel::Pixels);
+        assert_eq!(layout.cell_margin, 0);
+        assert_eq!(layout.cell_size, 0.5);
+    }
+
+    #[test]
+    fn zero_sized_grid() {
+        for grid_size in [(0, 0), (0, 10), (10, 0)] {
+            let layout = lay_out(&inputs((100, 100), grid_size, 2));
+            let Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } = layout.grid_rect;
+
+            assert!(layout.cell_size.is_finite() && layout.cell_size >= 0.);
+            assert!([x, y, width, height]
+                .into_iter()
+                .all(|side| side.is_finite() && side >= 0.));
+            assert!(width <= 100. && height <= 100.);
+        }
+    }
+
+    #[test]
+    fn scissor_rect_rounds_outwards() {
+        assert_eq!(
+            scissor_rect(Rectangle::new(10., 20., 30., 40.)),
+            (10, 20, 30, 40)
+        );
+        // NOTE: Truncating would give (10, 20, 30, 40), cutting off the
+        // partial pixels on every side
+        assert_eq!(
+            scissor_rect(Rectangle::new(10.5, 20.25, 30., 40.)),
+            (10, 20, 31, 41)
+        );
+        assert_eq!(
+            scissor_rect(Rectangle::new(-0.5, 0.5, 0.25, 0.)),
+            (-1, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn zero_sized_area() {
+        let layout = lay_out(&LayoutInputs {
+            area: Rectangle::new(0., 0., -10., 0.),
+            ..inputs((100, 100), (10, 10), 2)
+        });
+
+        assert_eq!(layout.cell_size, 0.);
+        assert!(!layout.covers_window);
+    }
+}
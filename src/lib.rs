@@ -23,6 +23,29 @@
 //!     frontend.tick();
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! - `bench`: exposes the internal helpers the frontends draw with to
+//!   the benchmarks (`cargo bench --features bench`), it's not meant to
+//!   be used otherwise.
+//! - `count-allocations`: counting of the allocations made by drawing
+//!   the grid, to check that it doesn't allocate once warmed up (see
+//!   the `alloc_count` module and `tests/allocations.rs`).
+//! - `gif`: recording of animated GIFs (see e.g.
+//!   `generic::RaylibFrontend::record_gif()`).
+//! - `rayon`: rendering the cells in parallel before drawing them,
+//!   for cells that are slow to render (requires them to be `Sync`,
+//!   see `MaybeSync`).
+//! - `serde`: saving and loading of the state of a frontend (see the
+//!   `session` module) and export of the cells as JSON (see the `json`
+//!   module).
+//! - `video`: export of videos through an external encoder (see the
+//!   `video` module).
+//! - `web`: building for the web with Emscripten (see
+//!   `examples/web.rs`), using the browser's clock (see the `clock`
+//!   module).  The frontends are driven one frame at a time with
+//!   `run_frame()` there, as the browser doesn't allow blocking loops.
 
 #![warn(
     clippy::pedantic,
@@ -50,6 +73,204 @@
     clippy::module_name_repetitions
 )]
 
+#[cfg(feature = "count-allocations")]
+pub mod alloc_count;
+#[cfg(feature = "gif")]
+mod animation;
+#[cfg(feature = "serde")]
+mod autosave;
+pub mod background;
+mod batch;
+pub mod clipboard;
+pub mod clock;
+pub mod color;
+pub mod convert;
 pub mod generic;
+mod graph;
+mod grid_cache;
+mod history;
+pub mod hud;
+#[cfg(feature = "serde")]
+pub mod json;
+mod layout;
+pub mod library;
 pub mod life_like;
-mod timer;
+pub mod overlay;
+mod pacer;
+pub mod patterns;
+mod pixel;
+mod recorder;
+#[cfg(feature = "serde")]
+pub mod session;
+mod shell;
+mod snapshot;
+mod stability;
+mod stats;
+mod stepper;
+pub mod timer;
+#[cfg(feature = "video")]
+pub mod video;
+
+/// The internal helpers the frontends draw with, exposed to the
+/// benchmarks so that they measure the same code.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    pub use crate::{
+        batch::{RectBatch, BATCH_THRESHOLD},
+        layout::{cell_coordinates, covers_window, fit_camera, lay_out, LayoutInputs},
+        snapshot::{DenseColors, GridSnapshot},
+    };
+}
+
+use std::fmt;
+
+use lifers::engine::ExecutionState;
+use raylib::{RaylibHandle, RaylibThread};
+
+use crate::hud::DEFAULT_TITLE;
+
+/// Bounds the cells have to meet to be rendered in parallel with the
+/// `rayon` feature, i.e. [`Sync`], met by any type without it.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Bounds the cells have to meet to be rendered in parallel with the
+/// `rayon` feature, i.e. [`Sync`], met by any type without it.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// The outcome of a single `tick()` of a frontend.
+#[derive(Default)]
+#[non_exhaustive]
+pub struct TickResult {
+    /// The number of generations computed.
+    pub steps: usize,
+    /// The state after the last computed generation, if any.
+    pub state: Option<ExecutionState>,
+}
+
+/// Why a frontend's `step_until()` stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StopReason {
+    /// The predicate returned `true`.
+    Predicate,
+    /// The generation limit was reached.
+    GenerationLimit,
+    /// The automaton finished.
+    Finished,
+    /// The window was closed in the meantime.
+    WindowClosed,
+}
+
+/// How much of the grid is drawn, reduced automatically when the cells
+/// get too small on the screen for the details to be visible (see e.g.
+/// `generic::FrontendBuilder::detail_thresholds()`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DetailLevel {
+    /// The cells are drawn as configured.
+    #[default]
+    Full,
+    /// The margins between the cells are dropped.
+    NoMargins,
+    /// The grid is drawn as a texture with a pixel per cell, as in
+    /// pixel mode.
+    Pixels,
+}
+
+impl DetailLevel {
+    /// Returns the level for cells of the given side on the screen,
+    /// `with_margins` and `without_margins` them, given the sizes the
+    /// margins and then the cells are dropped below.
+    pub(crate) fn for_cell_size(
+        (with_margins, without_margins): (f32, f32),
+        (no_margins_below, pixels_below): (f32, f32),
+    ) -> Self {
+        if without_margins < pixels_below {
+            Self::Pixels
+        } else if with_margins < no_margins_below {
+            Self::NoMargins
+        } else {
+            Self::Full
+        }
+    }
+}
+
+impl fmt::Display for DetailLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Full => "full",
+            Self::NoMargins => "no margins",
+            Self::Pixels => "pixels",
+        })
+    }
+}
+
+/// The cells that changed in a generation, as far as being alive goes.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct GenerationDiff {
+    /// Positions of the cells that came alive.
+    pub born: Vec<(usize, usize)>,
+    /// Positions of the cells that died.
+    pub died: Vec<(usize, usize)>,
+}
+
+impl GenerationDiff {
+    /// Removes all the positions, keeping the allocated memory.
+    pub(crate) fn clear(&mut self) {
+        self.born.clear();
+        self.died.clear();
+    }
+}
+
+/// Options of the window that have to be given when it's opened.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct WindowFlags {
+    pub transparent: bool,
+    pub undecorated: bool,
+    pub always_on_top: bool,
+    pub click_through: bool,
+}
+
+impl WindowFlags {
+    /// Opens the window with the given size.
+    ///
+    /// The flags are only hints, e.g. the window stays opaque where
+    /// transparency isn't supported.
+    #[allow(clippy::as_conversions)]
+    pub(crate) fn open(self, (width, height): (u32, u32)) -> (RaylibHandle, RaylibThread) {
+        let mut builder = raylib::init();
+
+        builder
+            .size(width as i32, height as i32)
+            .title(DEFAULT_TITLE);
+        if self.transparent {
+            builder.transparent();
+        }
+        if self.undecorated {
+            builder.undecorated();
+        }
+
+        let (mut rl, thread) = builder.build();
+
+        if self.always_on_top || self.click_through {
+            let state = rl
+                .get_window_state()
+                .set_window_topmost(self.always_on_top)
+                .set_window_mouse_passthrough(self.click_through);
+
+            rl.set_window_state(state);
+        }
+
+        (rl, thread)
+    }
+}
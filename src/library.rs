@@ -0,0 +1,214 @@
+//! A directory of pattern files to browse and place.
+
+use std::{
+    cell::OnceCell,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use raylib::drawing::RaylibDraw;
+
+use crate::{
+    hud::{Corner, HudStack, HudStyle, PADDING},
+    patterns::{self, Pattern},
+};
+
+/// Extensions of the files picked up by [`PatternLibrary::scan()`].
+const EXTENSIONS: [&str; 4] = ["rle", "cells", "lif", "life"];
+/// Marks the selected entry of the list.
+const SELECTED_MARKER: &str = "> ";
+/// Marks the rest of the entries of the list.
+const UNSELECTED_MARKER: &str = "  ";
+
+/// The pattern files found in a directory, each loaded when it's first
+/// needed.
+///
+/// A file failing to load doesn't affect the others, its error is kept
+/// instead (see [`errors()`](Self::errors())).
+#[derive(Debug, Default)]
+pub struct PatternLibrary {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// The file name without the extension.
+    name: String,
+    path: PathBuf,
+    pattern: OnceCell<Result<Pattern, String>>,
+}
+
+impl Entry {
+    fn pattern(&self) -> Result<&Pattern, &str> {
+        self.pattern
+            .get_or_init(|| patterns::load_pattern(&self.path).map_err(|error| error.to_string()))
+            .as_ref()
+            .map_err(String::as_str)
+    }
+}
+
+impl PatternLibrary {
+    /// Lists the pattern files (`.rle`, `.cells`, `.lif` and `.life`)
+    /// in a directory, not including its subdirectories, sorted by
+    /// name.
+    ///
+    /// Fails if the directory can't be read.  Entries that can't be
+    /// read are skipped.
+    pub fn scan(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut entries: Vec<_> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+                        })
+            })
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+
+                Some(Entry {
+                    name,
+                    path,
+                    pattern: OnceCell::new(),
+                })
+            })
+            .collect();
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the number of pattern files.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether no pattern files were found.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the names of the patterns (their file names without the
+    /// extension), in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Returns the index of the pattern with the given name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.name == name)
+    }
+
+    /// Returns the pattern at the given index, loading it if it wasn't
+    /// yet, or the error it failed to load with.
+    pub fn get(&self, index: usize) -> Option<Result<&Pattern, &str>> {
+        self.entries.get(index).map(Entry::pattern)
+    }
+
+    /// Returns the pattern with the given name, like [`get()`](Self::get()).
+    pub fn get_by_name(&self, name: &str) -> Option<Result<&Pattern, &str>> {
+        self.position(name).and_then(|index| self.get(index))
+    }
+
+    /// Loads all the patterns, returning the names of the ones that
+    /// failed along with the errors.
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().filter_map(|entry| {
+            entry
+                .pattern()
+                .err()
+                .map(|error| (entry.name.as_str(), error))
+        })
+    }
+
+    /// Draws the names around the selected one as a list, along with
+    /// the sizes of the patterns, or the errors of the ones that failed
+    /// to load.
+    ///
+    /// Only the listed patterns are loaded.
+    pub(crate) fn draw_list(
+        &self,
+        drawer: &mut impl RaylibDraw,
+        stack: &mut HudStack,
+        selected: usize,
+    ) {
+        let HudStyle {
+            font_size,
+            text_color,
+            ..
+        } = stack.style;
+        let corner = Corner::TopLeft;
+        let line_height = font_size.saturating_add(PADDING);
+        let rows_fit = stack
+            .available_height(corner)
+            .saturating_sub(PADDING)
+            .checked_div(line_height)
+            .unwrap_or(0);
+        let rows_n = usize::try_from(rows_fit)
+            .unwrap_or(0)
+            .clamp(1, self.entries.len().max(1));
+        // NOTE: Keeps the selected entry in the middle where possible
+        let first = selected
+            .saturating_sub(rows_n.checked_div(2).unwrap_or(0))
+            .min(self.entries.len().saturating_sub(rows_n));
+        let lines: Vec<_> = if self.entries.is_empty() {
+            vec!["No patterns found".to_owned()]
+        } else {
+            self.entries
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(rows_n)
+                .map(|(index, entry)| {
+                    let marker = if index == selected {
+                        SELECTED_MARKER
+                    } else {
+                        UNSELECTED_MARKER
+                    };
+
+                    match entry.pattern() {
+                        Ok(pattern) => format!(
+                            "{marker}{} ({}x{})",
+                            entry.name, pattern.width, pattern.height
+                        ),
+                        Err(error) => format!("{marker}{} (error: {error})", entry.name),
+                    }
+                })
+                .collect()
+        };
+        let rows = i32::try_from(lines.len()).unwrap_or(i32::MAX);
+        let size = (
+            lines
+                .iter()
+                .map(|line| stack.renderer.measure(line, font_size))
+                .max()
+                .unwrap_or(0)
+                .saturating_add(PADDING.saturating_mul(2)),
+            rows.saturating_mul(line_height).saturating_add(PADDING),
+        );
+        let (x, y) = stack.place(corner, size);
+
+        stack.draw_box(drawer, (x, y), size);
+
+        let renderer = stack.renderer;
+        let mut row_y = y.saturating_add(PADDING);
+
+        lines.iter().for_each(|line| {
+            renderer.draw(
+                drawer,
+                line,
+                x.saturating_add(PADDING),
+                row_y,
+                font_size,
+                text_color,
+            );
+
+            row_y = row_y.saturating_add(line_height);
+        });
+    }
+}
@@ -1,39 +1,228 @@
 //! Alternative implementation for life-like automata.
 
-use std::time::Duration;
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+use std::{
+    borrow::Cow, cell::Cell, fs, hash::BuildHasher, io, mem, ops::Range, path::Path, thread,
+    time::Duration,
+};
 
 use life_like::Automaton;
 use lifers::{engine::ExecutionState, prelude::*};
-use raylib::prelude::*;
+use raylib::{core::window, prelude::*};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use rustc_hash::{FxBuildHasher, FxHashSet};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(feature = "count-allocations")]
+use crate::alloc_count;
+#[cfg(feature = "gif")]
+use crate::animation::GifRecorder;
+#[cfg(feature = "serde")]
+use crate::autosave::Autosave;
+#[cfg(feature = "serde")]
+use crate::json::{self, ExportSelection};
+#[cfg(feature = "serde")]
+use crate::session::{self, SessionError, SessionState, View};
+#[cfg(feature = "video")]
+use crate::video::{VideoConfig, VideoExport};
 use crate::{
+    background::{Background, BackgroundCache},
+    batch::{RectBatch, BATCH_THRESHOLD},
+    clipboard::Clipboard,
+    clock::Instant,
+    convert,
+    graph::{Graph, DEFAULT_GRAPH_LENGTH},
+    grid_cache::GridCache,
+    history::History,
+    hud::{
+        format_update_rate, Corner, FrameStats, FrameTimer, GraphSeries, HudContext, HudStack,
+        HudStyle, Legend, RateUnit, StatusBar, StepStats, TextRenderer, DEFAULT_MAX_TOASTS,
+        DEFAULT_PAUSED_TEXT, DEFAULT_TITLE, DEFAULT_TITLE_FORMAT, DEFAULT_TOAST_DURATION,
+    },
+    layout::{self, Layout, LayoutInputs, DEFAULT_DETAIL_THRESHOLDS},
+    library::PatternLibrary,
     map_vecs,
-    timer::{RepeatingTimer, TimerState},
+    overlay::{Overlay, OverlayContext},
+    pacer::{FramePacer, DEFAULT_FRAME_TIME},
+    patterns::{self, Pattern, PatternSource, RleError},
+    pixel::{PixelCanvas, DEFAULT_PARTIAL_UPLOAD_THRESHOLD},
+    recorder::{self, FrameExport},
+    shell::Shell,
+    snapshot::{DenseColors, GridSnapshot},
+    stability::CycleDetector,
+    stepper::{Step, Stepper},
+    timer::{
+        frequency_from_period, period_from_frequency, RepeatingTimer, TimerState,
+        DEFAULT_MAX_CYCLES, DEFAULT_MAX_ELAPSED, DEFAULT_TURBO_BUDGET,
+    },
+    DetailLevel, GenerationDiff, MaybeSync, StopReason, TickResult, WindowFlags,
 };
 
+/// The smallest and the largest coordinates of a group of cells.
+pub type Bounds = ((usize, usize), (usize, usize));
+
+/// What the cached grid was drawn at: the generation, the number of
+/// edits (see [`GridSnapshot::invalidations()`]) and the zoom and the
+/// view offset (as bits).
+type GridKey = (u64, u64, [u32; 3]);
+
+/// The smallest allowed zoom factor.
+const MIN_ZOOM: f32 = 0.01;
+/// Color of the cells of a pattern waiting to be pasted (see
+/// [`RaylibFrontend::paste_from_clipboard()`]).
+const PASTE_GHOST_COLOR: Color = Color::new(255, 255, 255, 96);
+/// Color of the outline of the selected cells (see
+/// [`RaylibFrontend::select()`]).
+const SELECTION_COLOR: Color = Color::new(255, 200, 0, 255);
+/// Width of the outline of the selected cells, in pixels.
+const SELECTION_LINE_WIDTH: f32 = 2.;
+/// The cells are rendered into a dense buffer once per generation if
+/// there are at least one per this many visible positions, instead of
+/// looking every position up every frame.
+const DENSE_RATIO: usize = 4;
+
+/// How long to sleep instead of drawing a frame while the window is
+/// minimized and the simulation is paused.
+const MINIMIZED_FRAME_TIME: Duration = Duration::from_millis(50);
+/// Default keys to save and load the state (see
+/// [`RaylibFrontend::quick_save_actions()`]).
+const DEFAULT_QUICK_SAVE_KEYS: (KeyboardKey, KeyboardKey) =
+    (KeyboardKey::KEY_F5, KeyboardKey::KEY_F9);
+/// Default file to save the state to (see
+/// [`RaylibFrontend::session_actions()`]).
+#[cfg(feature = "serde")]
+const DEFAULT_SESSION_PATH: &str = "session.json";
+/// Default key to record a GIF (see [`RaylibFrontend::gif_actions()`]).
+#[cfg(feature = "gif")]
+const DEFAULT_GIF_KEY: KeyboardKey = KeyboardKey::KEY_G;
+/// The number of generations recorded by
+/// [`RaylibFrontend::gif_actions()`].
+#[cfg(feature = "gif")]
+const GIF_ACTION_GENERATIONS: usize = 100;
+/// Pixels per cell in GIFs recorded by
+/// [`RaylibFrontend::gif_actions()`].
+#[cfg(feature = "gif")]
+const GIF_ACTION_SCALE: usize = 4;
+
+/// How the margin between cells reacts to zooming (see
+/// [`RaylibFrontend::set_zoom()`]).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarginMode {
+    /// The margin stays the same number of pixels.
+    Screen,
+    /// The margin is scaled along with the cells, but kept within the
+    /// given number of pixels.
+    World { min: f32, max: f32 },
+}
+
+impl Default for MarginMode {
+    fn default() -> Self {
+        Self::World {
+            min: 0.,
+            max: f32::INFINITY,
+        }
+    }
+}
+
 /// A version of [`RaylibFrontend`](crate::generic::RaylibFrontend)
 /// that works with
 /// [`life_like::Automaton`](lifers::engine::life_like::Automaton).
 pub struct RaylibFrontend<S, D> {
     // TODO: Generalize
     automaton: Automaton<S, D>,
+    pacer: FramePacer,
+    // NOTE: The fields owning GPU resources, which have to be dropped
+    // before the window is closed (also by `into_automaton()`)
+    canvas: PixelCanvas,
+    grid_cache: GridCache<GridKey>,
+    font: Option<Font>,
     rl: RaylibHandle,
     thread: RaylibThread,
     timer: RepeatingTimer,
-    // TODO?: Use fractional size and render partial cells
     grid_size: (usize, usize),
-    default_color: Color,
-    cell_margin: u32,
-    rect_size: f32,
-    center_translation: Vector2,
+    background: Background,
+    background_cache: BackgroundCache,
+    /// The margin between cells as configured.
+    full_margin: u32,
+    detail_thresholds: (f32, f32),
+    /// The grid as last laid out at zoom `1.0` (see
+    /// [`update_layout()`](Self::update_layout())).
+    layout: Layout,
+    /// Whether anything the layout depends on changed since.
+    layout_dirty: bool,
+    /// The window size to restore when leaving fullscreen.
+    windowed_size: Option<(i32, i32)>,
+    always_clear: bool,
+    clear_color: Color,
+    view_offset: Vector2,
+    zoom: f32,
+    margin_mode: MarginMode,
+    overlays: Vec<Overlay>,
+    batch: RectBatch,
+    legend: Legend,
+    pixel_mode: bool,
+    generation: u64,
+    shell: Shell<Automaton<S, D>, Self>,
+    partial_cells: bool,
+    status_bar: Option<StatusBar>,
+    graph: Option<Graph>,
+    graph_source: Option<Box<dyn Fn(&Automaton<S, D>) -> f32>>,
+    graph_series: GraphSeries,
+    track_step_stats: bool,
+    step_stats: Option<StepStats>,
+    max_generations: Option<u64>,
+    frame_timer: FrameTimer,
+    max_steps_per_frame: u32,
+    close_on_finish: bool,
+    cycle_detector: Option<CycleDetector<FxHashSet<(usize, usize)>>>,
+    pause_on_cycle: bool,
+    on_cycle: Option<Box<dyn FnMut(usize, u64)>>,
+    dpi_scale: Option<f32>,
+    snapshot: GridSnapshot,
+    dense_colors: DenseColors,
+    headless: bool,
+    close_requested: bool,
+    quick_save: Option<StateSnapshot<S, D>>,
+    quick_save_keys: (KeyboardKey, KeyboardKey),
+    #[cfg(feature = "gif")]
+    gif_key: KeyboardKey,
+    #[cfg(feature = "serde")]
+    session_path: PathBuf,
+    live_bounds: Cell<Option<Option<Bounds>>>,
+    on_step: Option<Box<dyn FnMut(u64, &Automaton<S, D>)>>,
+    on_finish: Option<Box<dyn FnOnce(&Automaton<S, D>)>>,
+    track_diff: bool,
+    diff: Option<GenerationDiff>,
+    frame_export: Option<FrameExport>,
+    paste_cell: Option<Box<dyn Fn() -> S>>,
+    /// The screen rectangles of the pasted pattern at the cursor,
+    /// reused across frames.
+    paste_ghost: Vec<Rectangle>,
+    selection: Option<Bounds>,
+    /// The cell a selection is being dragged from.
+    selection_anchor: Option<(usize, usize)>,
+    /// The colors of the cells rendered in parallel, reused across
+    /// frames.
+    #[cfg(feature = "rayon")]
+    rendered_colors: Vec<Option<Color>>,
+    /// The positions of the visible columns and rows, reused across
+    /// frames.
+    column_xs: Vec<f32>,
+    row_ys: Vec<f32>,
+    cursor: MouseCursor,
+    cursor_hidden: bool,
+    #[cfg(feature = "video")]
+    video_export: Option<VideoExport>,
 }
 
 impl<S, D> RaylibFrontend<S, D> {
-    // NOTE: This function is quite a mess
     /// Instantiates the frontend.
     ///
     /// You may want to use [`FrontendBuilder`] for convenience.
-    #[allow(clippy::as_conversions)]
     pub fn new(
         automaton: Automaton<S, D>,
         init_grid_size: (usize, usize),
@@ -42,202 +231,4497 @@ impl<S, D> RaylibFrontend<S, D> {
         cell_margin: u32,
         window_size: (u32, u32),
     ) -> Self {
-        let (rl, thread) = raylib::init()
-            .size(window_size.0 as i32, window_size.1 as i32)
-            .title("lifers")
-            .build();
-
-        let cell_margin_f = cell_margin as f32;
-        let window_size = Vector2::new(window_size.0 as f32, window_size.1 as f32);
-
-        let grid_dimensions = Vector2::new(init_grid_size.0 as f32, init_grid_size.1 as f32);
-        let rect_size = {
-            let Vector2 { x, y } = map_vecs!(
-                window_size,
-                grid_dimensions
-                => |win, cells: f32| (cells + 1.).mul_add(-cell_margin_f, win) / cells
-            );
-            let side = x.min(y);
-
-            Vector2::new(side, side)
-        };
-        let grid_size = map_vecs!(
-            rect_size,
-            grid_dimensions
-            => |size, cells: f32| cells.mul_add(size, (cells + 1.) * cell_margin_f)
-        );
-        let grid_center = grid_size.scale_by(0.5);
-        let window_center = window_size.scale_by(0.5);
-
-        // NOTE: `grid_center` is calculated with respect to the window dimensions,
-        // so it can't be greater than `window_center`
-        #[allow(clippy::arithmetic_side_effects)]
-        let center_translation = window_center - grid_center;
+        Self::with_window(
+            automaton,
+            init_grid_size,
+            Background::Solid(default_color),
+            update_rate,
+            cell_margin,
+            WindowFlags::default().open(window_size),
+            false,
+        )
+    }
 
-        Self {
+    /// Instantiates the frontend in an already opened window.
+    fn with_window(
+        automaton: Automaton<S, D>,
+        init_grid_size: (usize, usize),
+        background: Background,
+        update_rate: Duration,
+        cell_margin: u32,
+        (rl, thread): (RaylibHandle, RaylibThread),
+        partial_cells: bool,
+    ) -> Self {
+        let mut frontend = Self {
             automaton,
             rl,
             thread,
             timer: RepeatingTimer::new(update_rate),
             grid_size: init_grid_size,
-            default_color,
-            cell_margin,
-            rect_size: rect_size.x,
-            center_translation,
+            background,
+            background_cache: BackgroundCache::default(),
+            full_margin: cell_margin,
+            detail_thresholds: DEFAULT_DETAIL_THRESHOLDS,
+            layout: Layout::default(),
+            layout_dirty: true,
+            windowed_size: None,
+            partial_cells,
+            always_clear: false,
+            clear_color: Color::GRAY,
+            view_offset: Vector2::zero(),
+            zoom: 1.,
+            margin_mode: MarginMode::default(),
+            overlays: Vec::new(),
+            batch: RectBatch::default(),
+            legend: Legend::new(),
+            pixel_mode: false,
+            canvas: PixelCanvas::new(TextureFilter::TEXTURE_FILTER_POINT),
+            grid_cache: GridCache::new(false),
+            pacer: FramePacer::new(Some(DEFAULT_FRAME_TIME)),
+            font: None,
+            generation: 0,
+            shell: Shell::new(),
+            status_bar: None,
+            graph: None,
+            graph_source: None,
+            graph_series: GraphSeries::default(),
+            track_step_stats: false,
+            step_stats: None,
+            max_generations: None,
+            frame_timer: FrameTimer::new(),
+            max_steps_per_frame: DEFAULT_MAX_CYCLES,
+            close_on_finish: false,
+            cycle_detector: None,
+            pause_on_cycle: false,
+            on_cycle: None,
+            dpi_scale: None,
+            snapshot: GridSnapshot::new(),
+            dense_colors: DenseColors::new(),
+            headless: false,
+            close_requested: false,
+            quick_save: None,
+            quick_save_keys: DEFAULT_QUICK_SAVE_KEYS,
+            #[cfg(feature = "gif")]
+            gif_key: DEFAULT_GIF_KEY,
+            #[cfg(feature = "serde")]
+            session_path: PathBuf::from(DEFAULT_SESSION_PATH),
+            live_bounds: Cell::new(None),
+            on_step: None,
+            on_finish: None,
+            track_diff: false,
+            diff: None,
+            frame_export: None,
+            paste_cell: None,
+            paste_ghost: Vec::new(),
+            selection: None,
+            selection_anchor: None,
+            #[cfg(feature = "rayon")]
+            rendered_colors: Vec::new(),
+            column_xs: Vec::new(),
+            row_ys: Vec::new(),
+            cursor: MouseCursor::MOUSE_CURSOR_DEFAULT,
+            cursor_hidden: false,
+            #[cfg(feature = "video")]
+            video_export: None,
+        };
+
+        frontend.update_layout();
+
+        frontend
+    }
+
+    /// Collects everything the layout of the grid depends on: the
+    /// window, the status bar, the grid size and the margins.
+    ///
+    /// The zoom and the view offset aren't part of it, they're applied
+    /// to the layout when drawing.
+    fn layout_inputs(&self) -> LayoutInputs {
+        let window_size = self.window_size();
+        let window_rect = layout::window_rect(window_size);
+
+        LayoutInputs {
+            window_size,
+            area: self
+                .status_bar
+                .as_ref()
+                .map_or(window_rect, |bar| bar.split(window_rect).1),
+            grid_size: self.grid_size,
+            band: 0,
+            margin: self.full_margin,
+            detail_thresholds: self.detail_thresholds,
+            // NOTE: With partial cells the grid takes up the whole area,
+            // the cells that don't fit entirely are clipped
+            fill_area: self.partial_cells,
         }
     }
 
-    /// Checks if the window should close (e.g. `esc` pressed).
-    pub fn window_should_close(&self) -> bool {
-        self.automaton.is_finished() || self.rl.window_should_close()
+    /// Marks the layout of the grid as out of date, it's computed again
+    /// at the start of the next frame (see
+    /// [`update_layout()`](Self::update_layout())).
+    fn invalidate_layout(&mut self) {
+        self.layout_dirty = true;
     }
 
-    /// Updates the inner timer to compute the next generation according
-    /// to the update rate (see [`FrontendBuilder::update_rate()`]).
-    pub fn tick(&mut self) -> Option<ExecutionState> {
-        matches!(self.timer.update(), TimerState::Finished).then(|| self.automaton.step())
+    /// Lays out the grid again if anything it depends on changed since
+    /// it was last laid out (see
+    /// [`invalidate_layout()`](Self::invalidate_layout())), dropping
+    /// everything rendered for the old layout.
+    fn update_layout(&mut self) {
+        if !self.layout_dirty {
+            return;
+        }
+
+        self.layout = layout::lay_out(&self.layout_inputs());
+        self.layout_dirty = false;
+        self.snapshot.invalidate();
+        self.background_cache.invalidate();
     }
 
-    /// Computes the next generation of the automaton immediately.
+    /// Sets the entries of the color legend, replacing the previous
+    /// ones.
     ///
-    /// See [`tick()`](Self::tick()) for properly timed updating.
-    pub fn step(&mut self) -> ExecutionState {
-        self.automaton.step()
+    /// The legend is drawn by [`display_grid()`](Self::display_grid())
+    /// unless there are no entries or it's toggled off.
+    pub fn set_legend<L: Into<String>>(&mut self, entries: impl IntoIterator<Item = (L, Color)>) {
+        self.legend.entries = entries
+            .into_iter()
+            .map(|(label, color)| (label.into(), color))
+            .collect();
     }
 
-    /// Registers default key actions:
-    /// - Space -> Pause
-    /// - LMB -> Toggle cell under cursor
-    pub fn default_key_actions(&mut self) {
-        match self.rl.get_key_pressed() {
-            None => (),
-            Some(key) => match key {
-                KeyboardKey::KEY_SPACE => self.timer.toggle_pause(), // HACK?
-                // NOTE: Minus reduces the rate (not the time taken), equals
-                // increases the rate.
-                KeyboardKey::KEY_MINUS => {
-                    self.timer = RepeatingTimer::new(self.timer.rate() + Duration::from_millis(10))
-                }
-                KeyboardKey::KEY_EQUAL => {
-                    let duration = self
-                        .timer
-                        .rate()
-                        .checked_sub(Duration::from_millis(10))
-                        .unwrap_or(Duration::from_millis(0));
+    /// Shows or hides the color legend.
+    pub fn toggle_legend(&mut self) {
+        self.legend.visible = !self.legend.visible;
+    }
 
-                    self.timer = RepeatingTimer::new(duration);
-                }
-                _ => (),
-            },
+    /// Sets how "dead" cells are drawn (see [`Background`]).
+    pub fn set_background(&mut self, background: impl Into<Background>) {
+        self.background = background.into();
+        self.invalidate_render_cache();
+    }
+
+    /// Returns the filter used when the grid texture is scaled in pixel
+    /// mode.
+    pub const fn texture_filter(&self) -> TextureFilter {
+        self.canvas.filter()
+    }
+
+    /// Sets the filter used when the grid texture is scaled in pixel
+    /// mode (see [`FrontendBuilder::texture_filter()`]).
+    pub fn set_texture_filter(&mut self, filter: TextureFilter) {
+        self.canvas.set_filter(&self.thread, filter);
+        self.invalidate_render_cache();
+    }
+
+    /// Makes the next frame render the grid anew instead of reusing
+    /// what was rendered for the last one (see
+    /// [`FrontendBuilder::cache_grid()`]).
+    ///
+    /// The setters changing how cells are colored call this already, it
+    /// only has to be called if the colors of the cells change without
+    /// the cells themselves changing, e.g. if they're converted to
+    /// colors by reading some outside state.
+    pub fn invalidate_render_cache(&mut self) {
+        self.snapshot.invalidate();
+        self.background_cache.invalidate();
+        self.grid_cache.invalidate();
+    }
+
+    /// Shows or hides the FPS counter.
+    pub fn toggle_fps(&mut self) {
+        self.shell.hud.show_fps = !self.shell.hud.show_fps;
+    }
+
+    /// Shows or hides the time taken by steps and drawing.
+    pub fn toggle_timings(&mut self) {
+        self.shell.hud.show_timings = !self.shell.hud.show_timings;
+    }
+
+    /// Returns the average time taken by steps and drawing.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_timer.stats()
+    }
+
+    /// Makes the grid be laid out again if the window was resized (by
+    /// the user or the window manager) since it was last laid out.
+    ///
+    /// The window size is compared directly, so resizes are noticed
+    /// even if the window isn't resizable.  It's called by
+    /// [`display_grid()`](Self::display_grid()) every frame, right
+    /// before the layout is brought up to date, and before rendering
+    /// into images, so there's no need to call it otherwise.
+    pub fn handle_window_events(&mut self) {
+        let window_size = self.window_size();
+
+        if !self.rl.is_window_resized() && window_size == self.layout.window_size {
+            return;
         }
+
+        self.invalidate_layout();
     }
-}
 
-impl<S: RenderCell<Color>, D> RaylibFrontend<S, D> {
-    /// Displays the cell grid using Raylib.
+    /// Returns the ratio of the framebuffer size to the window size,
+    /// e.g. `2` on HiDPI displays with 2x scaling.
     ///
-    /// Manages the job of clearing the background and drawing all the
-    /// cells with respect to their [`RenderCell`] implementation.
-    pub fn display_grid(&mut self) {
-        let mut drawer = self.rl.begin_drawing(&self.thread);
+    /// The grid is laid out in logical units, like the mouse position
+    /// is given in (see [`mouse_position()`](Self::mouse_position())),
+    /// and raylib scales what's drawn on the window to the framebuffer.
+    /// The scale is only applied where raylib doesn't, to the textures
+    /// the window is rendered into (the grid cache and the exported
+    /// frames), so that they are as sharp as the window.
+    #[allow(clippy::as_conversions)]
+    pub fn dpi_scale(&self) -> f32 {
+        self.dpi_scale.unwrap_or_else(|| {
+            let window_width = self.rl.get_screen_width();
+
+            if window_width > 0 {
+                self.rl.get_render_width() as f32 / window_width as f32
+            } else {
+                1.
+            }
+        })
+    }
+
+    /// Overrides the DPI scale (see [`dpi_scale()`](Self::dpi_scale())),
+    /// or goes back to querying it if [`None`] is given, laying out the
+    /// grid again.
+    pub fn set_dpi_scale(&mut self, dpi_scale: Option<f32>) {
+        self.dpi_scale = dpi_scale.filter(|scale| *scale > 0.);
+        self.invalidate_layout();
+    }
 
-        drawer.clear_background(Color::GRAY);
+    /// Returns the size of the window in logical units (see
+    /// [`dpi_scale()`](Self::dpi_scale())).
+    fn window_size(&self) -> (i32, i32) {
+        (self.rl.get_screen_width(), self.rl.get_screen_height())
+    }
+
+    /// Returns the size of the framebuffer, i.e. of the window scaled by
+    /// [`dpi_scale()`](Self::dpi_scale()).
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss
+    )]
+    fn framebuffer_size(&self) -> (u32, u32) {
+        let scale = self.dpi_scale();
+        let (width, height) = self.window_size();
+        let scaled = |side: i32| u32::try_from((side as f32 * scale).round() as i32).unwrap_or(0);
+
+        (scaled(width), scaled(height))
+    }
+
+    /// Returns the position of the mouse in the coordinates the grid is
+    /// drawn in, to be used with e.g.
+    /// [`screen_to_cell()`](Self::screen_to_cell()).
+    pub fn mouse_position(&self) -> Vector2 {
+        self.rl.get_mouse_position()
+    }
+
+    /// Keeps the window above the other ones, or stops doing so.
+    ///
+    /// It's supported on the desktop platforms (though Wayland
+    /// compositors may ignore it), elsewhere it does nothing but show a
+    /// toast.
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        let state = self.rl.get_window_state().set_window_topmost(always_on_top);
+
+        self.rl.set_window_state(state);
+        if self.is_always_on_top() != always_on_top {
+            self.toast("Keeping the window on top isn't supported");
+        }
+    }
+
+    /// Checks whether the window is kept above the other ones (see
+    /// [`set_always_on_top()`](Self::set_always_on_top())).
+    pub fn is_always_on_top(&self) -> bool {
+        self.rl.get_window_state().window_topmost()
+    }
+
+    /// Lets the mouse clicks go to the windows underneath, e.g. to keep
+    /// the automaton animating over the desktop, or catches them again.
+    /// The input from the keyboard is unaffected.
+    ///
+    /// It's supported on the desktop platforms (though Wayland
+    /// compositors may ignore it), elsewhere it does nothing but show a
+    /// toast.
+    pub fn set_click_through(&mut self, click_through: bool) {
+        let state = self
+            .rl
+            .get_window_state()
+            .set_window_mouse_passthrough(click_through);
+
+        self.rl.set_window_state(state);
+        if self.is_click_through() != click_through {
+            self.toast("Clicking through the window isn't supported");
+        }
+    }
+
+    /// Checks whether the mouse clicks go to the windows underneath (see
+    /// [`set_click_through()`](Self::set_click_through())).
+    pub fn is_click_through(&self) -> bool {
+        self.rl.get_window_state().window_mouse_passthrough()
+    }
+
+    /// Checks whether the window is fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.rl.is_window_fullscreen()
+    }
+
+    /// Switches the window to fullscreen or back, laying out the grid
+    /// again.
+    ///
+    /// Fullscreen takes the resolution of the monitor the window is on,
+    /// the size of the window is restored when leaving it.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        if fullscreen == self.is_fullscreen() {
+            return;
+        }
+
+        if fullscreen {
+            let monitor = window::get_current_monitor();
 
-        (0..self.grid_size.0).for_each(|x| (0..self.grid_size.1).for_each(|y| {
-            let pos = map_vecs!(
-                Vector2::new(x as f32, y as f32),
-                self.center_translation
-                => |pos: f32, center_vec| pos.mul_add(self.rect_size, (pos + 1.) * self.cell_margin as f32) + center_vec
+            self.windowed_size = Some((self.rl.get_screen_width(), self.rl.get_screen_height()));
+            self.rl.set_window_size(
+                window::get_monitor_width(monitor),
+                window::get_monitor_height(monitor),
             );
-            // HACK: Unify types (`usize`)
-            let cell = self.automaton.cells().get(&(x, y));
-            let color = cell.map_or(self.default_color, |c| c.render_cell());
+            self.rl.toggle_fullscreen();
+        } else {
+            self.rl.toggle_fullscreen();
+            if let Some((width, height)) = self.windowed_size.take() {
+                self.rl.set_window_size(width, height);
+            }
+        }
 
-            let rect = Vector2::new(self.rect_size, self.rect_size);
-            drawer.draw_rectangle_v(pos, rect, color);
-        }))
+        self.invalidate_layout();
     }
-}
 
-/// A helper struct to instantiate a [`RaylibFrontend`].
-pub struct FrontendBuilder {
-    window_size: (u32, u32),
-    cell_margin: u32,
-    update_rate: Duration,
-    init_grid_size: (usize, usize),
-    default_color: Color,
-}
+    /// Switches the window to fullscreen or back (see
+    /// [`set_fullscreen()`](Self::set_fullscreen())).
+    pub fn toggle_fullscreen(&mut self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
 
-impl FrontendBuilder {
-    /// Sets the window size.
-    pub const fn window_size(self, window_size: (u32, u32)) -> Self {
-        Self {
-            window_size,
-            ..self
+    /// Sets the status bar, or removes it if [`None`] is given.
+    ///
+    /// The grid is laid out again to make room for it.
+    pub fn set_status_bar(&mut self, status_bar: Option<StatusBar>) {
+        self.status_bar = status_bar;
+        self.invalidate_layout();
+    }
+
+    /// Returns the number of cells shown along each axis.
+    pub const fn grid_size(&self) -> (usize, usize) {
+        self.grid_size
+    }
+
+    /// Sets the number of cells shown along each axis, laying out the
+    /// grid again.
+    ///
+    /// Cells outside of the new size are kept, but not shown.  Fails if
+    /// either of the dimensions is `0`.
+    pub fn set_grid_size(&mut self, grid_size: (usize, usize)) -> Result<(), String> {
+        if grid_size.0 == 0 || grid_size.1 == 0 {
+            return Err(format!(
+                "grid size must not be zero, got {}x{}",
+                grid_size.0, grid_size.1
+            ));
         }
+
+        self.grid_size = grid_size;
+        self.invalidate_layout();
+
+        Ok(())
     }
 
-    /// Sets the cell margin (purely visual).
-    #[must_use]
-    pub const fn cell_margin(self, cell_margin: u32) -> Self {
-        Self {
+    /// Loads the font used for all the text (the HUD, legend, toasts,
+    /// etc.) from a file, replacing the previous one.
+    ///
+    /// `size` is the size the glyphs are rasterized at, text drawn at
+    /// other sizes is scaled.
+    pub fn load_font(&mut self, path: &str, size: i32) -> Result<(), String> {
+        self.font = Some(self.rl.load_font_ex(&self.thread, path, size, None)?);
+
+        Ok(())
+    }
+
+    /// Sets the window title.
+    ///
+    /// The statistics are appended to it if enabled (see
+    /// [`FrontendBuilder::title_stats()`]), and kept up to date.
+    ///
+    /// Setting the same title again does nothing, but changing it every
+    /// frame is best avoided, as some window managers are slow to keep
+    /// up.  Null characters are left out, as the window can't show them.
+    pub fn set_window_title(&mut self, title: impl Into<Cow<'static, str>>) {
+        self.shell.title = title.into();
+        self.update_title();
+    }
+
+    /// Sets the statistics shown in the window title (see
+    /// [`FrontendBuilder::title_format()`]), or removes them if [`None`]
+    /// is given.
+    pub fn set_title_format(&mut self, title_format: Option<Cow<'static, str>>) {
+        self.shell.title_format = title_format;
+        self.update_title();
+    }
+
+    /// Shows the title along with the statistics, if enabled.
+    fn update_title(&mut self) {
+        let population = Some(self.population());
+
+        if let Some(title) = self.shell.update_title(self.generation, population) {
+            self.rl.set_window_title(&self.thread, title);
+        }
+    }
+
+    /// Shows a short message for a couple of seconds (see
+    /// [`toast_for()`](Self::toast_for())).
+    pub fn toast(&mut self, text: impl Into<Cow<'static, str>>) {
+        self.toast_for(text, DEFAULT_TOAST_DURATION);
+    }
+
+    /// Shows a short message for the given duration.
+    ///
+    /// Messages shown at the same time are stacked, the oldest ones
+    /// are dropped if there are more than
+    /// [`FrontendBuilder::max_toasts()`].
+    pub fn toast_for(&mut self, text: impl Into<Cow<'static, str>>, duration: Duration) {
+        self.shell.toasts.push(text.into(), duration);
+    }
+
+    /// Returns the texts of the messages shown at the moment (see
+    /// [`toast()`](Self::toast())), oldest first.
+    pub fn toasts(&self) -> impl Iterator<Item = &str> {
+        self.shell.toasts.texts()
+    }
+
+    /// Sets a callback drawing a custom HUD, replacing the previous
+    /// one.
+    ///
+    /// It's called by [`display_grid()`](Self::display_grid()) after
+    /// the built-in HUD elements and the overlays (see
+    /// [`add_overlay()`](Self::add_overlay())) are drawn.
+    pub fn set_hud<F>(&mut self, hud: F)
+    where
+        F: FnMut(&mut RaylibDrawHandle<'_>, &HudContext) + 'static,
+    {
+        self.shell.hud_callback = Some(Box::new(hud));
+    }
+
+    /// Registers an overlay that's drawn on top of the grid by
+    /// [`display_grid()`](Self::display_grid()).
+    ///
+    /// Overlays are drawn in the order they were added.
+    pub fn add_overlay<F>(&mut self, overlay: F)
+    where
+        F: FnMut(&mut RaylibDrawHandle<'_>, &OverlayContext) + 'static,
+    {
+        self.overlays.push(Box::new(overlay));
+    }
+
+    /// Returns the current layout of the grid on the screen.
+    pub fn overlay_context(&self) -> OverlayContext {
+        let cell_margin = self.scaled_margin();
+        let pitch = self.cell_size() + cell_margin;
+
+        OverlayContext {
+            grid_rect: self.layout.grid_rect,
+            origin: map_vecs!(
+                self.view_offset,
+                self.layout.origin
+                => |offset: f32, center_vec: f32| (-offset).mul_add(pitch, center_vec + cell_margin)
+            ),
+            cell_size: self.cell_size(),
             cell_margin,
-            ..self
         }
     }
 
-    /// Sets the update rate.
+    /// Returns the zoom factor (`1.0` by default).
+    pub const fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Sets the zoom factor, scaling the cells around the top-left
+    /// corner of the view.
     ///
-    /// This is the amount of time that passes between each generation
-    /// is computed and displayed.
-    #[must_use]
-    pub const fn update_rate(self, update_rate: Duration) -> Self {
-        Self {
-            update_rate,
-            ..self
+    /// The margin between cells is scaled according to
+    /// [`FrontendBuilder::margin_mode()`].
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(MIN_ZOOM);
+    }
+
+    /// Returns the side of a cell on the screen, taking zoom into
+    /// account.
+    fn cell_size(&self) -> f32 {
+        self.layout.cell_size * self.zoom
+    }
+
+    /// Returns how much of the grid is drawn at the current cell size,
+    /// taking zoom into account (see
+    /// [`FrontendBuilder::detail_thresholds()`]).
+    pub fn detail_level(&self) -> DetailLevel {
+        let (with_margins, without_margins) = self.layout.fitted_cell_sizes;
+
+        DetailLevel::for_cell_size(
+            (with_margins * self.zoom, without_margins * self.zoom),
+            self.detail_thresholds,
+        )
+    }
+
+    /// Returns the margin between cells on the screen, taking zoom into
+    /// account.
+    #[allow(clippy::as_conversions)]
+    fn scaled_margin(&self) -> f32 {
+        if self.detail_level() != DetailLevel::Full {
+            return 0.;
+        }
+
+        let cell_margin = self.full_margin as f32;
+
+        match self.margin_mode {
+            MarginMode::Screen => cell_margin,
+            MarginMode::World { min, max } => (cell_margin * self.zoom).max(min).min(max),
         }
     }
 
-    /// Sets the initial grid size.
+    /// Scrolls the view so that the given (possibly fractional) cell
+    /// position is at the top-left corner of the grid.
     ///
-    /// Since the life-like automata don't care about the grid size,
-    /// this value can later be changed in [`RaylibFrontend`].
-    pub const fn grid_size(self, init_grid_size: (usize, usize)) -> Self {
-        Self {
-            init_grid_size,
-            ..self
+    /// Negative values are clamped to `0`.
+    pub fn set_view_offset(&mut self, (x, y): (f32, f32)) {
+        self.view_offset = Vector2::new(x.max(0.), y.max(0.));
+    }
+
+    /// Returns the ranges of cells that are (at least partially)
+    /// visible along both axes.
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn visible_cells(&self) -> (Range<usize>, Range<usize>) {
+        let pitch = self.cell_size() + self.scaled_margin();
+        let range = |offset: f32, length: f32| {
+            let first = offset.floor() as usize;
+            let count = (length / pitch).ceil() as usize;
+
+            first..first.saturating_add(count).saturating_add(1)
+        };
+
+        (
+            range(self.view_offset.x, self.layout.grid_rect.width),
+            range(self.view_offset.y, self.layout.grid_rect.height),
+        )
+    }
+
+    /// Collects the values shown by the HUD.
+    fn hud_context(&self, context: &OverlayContext) -> HudContext {
+        HudContext {
+            generation: self.generation,
+            population: Some(self.population()),
+            fps: self.rl.get_fps(),
+            frame_time: self.rl.get_frame_time(),
+            paused: self.is_paused(),
+            focus_paused: self.shell.focus_paused && self.is_paused(),
+            update_rate: self.timer.rate(),
+            measured_rate: self.shell.rate_meter.rate(),
+            max_generations: self.max_generations,
+            step_stats: self.step_stats,
+            frame_stats: self.frame_timer.stats(),
+            turbo: self.shell.turbo,
+            recording: self.is_recording(),
+            last_autosave: self.shell.last_autosave,
+            window_size: self.window_size(),
+            grid_rect: context.grid_rect,
+            detail: self.detail_level(),
         }
     }
 
-    /// Sets the color for "dead" cells.
-    pub const fn default_color(self, default_color: Color) -> Self {
-        Self {
-            default_color,
-            ..self
+    /// Checks whether frames are being exported in any way.
+    fn is_recording(&self) -> bool {
+        #[cfg(feature = "video")]
+        if self.video_export.is_some() {
+            return true;
         }
+
+        self.frame_export.is_some()
     }
 
-    /// Converts the builder to an actual [`RaylibFrontend`].
-    pub fn finish<S, D>(self, automaton: Automaton<S, D>) -> RaylibFrontend<S, D> {
-        RaylibFrontend::new(
-            automaton,
-            self.init_grid_size,
-            self.default_color,
-            self.update_rate,
-            self.cell_margin,
-            self.window_size,
+    /// Returns the underlying raylib handle, e.g. to load sounds or
+    /// query the monitors.
+    ///
+    /// Here be dragons: the frontend assumes it's the only one drawing
+    /// and changing the window, so don't call `begin_drawing()` (use
+    /// [`add_overlay()`](Self::add_overlay()) or
+    /// [`display_grid_with()`](Self::display_grid_with()) instead), and
+    /// expect changes to the window (e.g. its size) to be overridden.
+    pub fn raylib_handle(&mut self) -> &mut RaylibHandle {
+        &mut self.rl
+    }
+
+    /// Returns the raylib thread token, to be used with
+    /// [`raylib_handle()`](Self::raylib_handle()) (with the same
+    /// caveats).
+    pub const fn raylib_thread(&self) -> &RaylibThread {
+        &self.thread
+    }
+
+    /// Returns the automaton being displayed.
+    pub const fn automaton(&self) -> &Automaton<S, D> {
+        &self.automaton
+    }
+
+    /// Returns the automaton being displayed, to read or change its
+    /// cells.
+    ///
+    /// Mutating the cells is fine and is shown by the next
+    /// [`display_grid()`](Self::display_grid()), but only the cells
+    /// within the grid size given to the builder are visible.
+    pub fn automaton_mut(&mut self) -> &mut Automaton<S, D> {
+        // NOTE: The cells may change without a new generation
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+
+        self.cancel_steps();
+        &mut self.automaton
+    }
+
+    /// Returns the smallest and the largest coordinates of the cells
+    /// (inclusive), or [`None`] if there are none.
+    ///
+    /// It's only computed once per generation.
+    pub fn live_bounds(&self) -> Option<Bounds> {
+        if let Some(bounds) = self.live_bounds.get() {
+            return bounds;
+        }
+
+        let bounds = self.automaton.cells().keys().fold(None, |bounds, &(x, y)| {
+            Some(
+                bounds.map_or(((x, y), (x, y)), |((min_x, min_y), (max_x, max_y))| {
+                    ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+                }),
+            )
+        });
+        self.live_bounds.set(Some(bounds));
+
+        bounds
+    }
+
+    /// Returns the cells within the grid size (see
+    /// [`set_grid_size()`](Self::set_grid_size())) as a grid indexed as
+    /// `grid[y][x]`, with the empty positions filled by `dead`.
+    ///
+    /// This is the representation used by generic automata (see
+    /// [`convert`](crate::convert)).
+    pub fn dense_cells(&self, dead: impl FnMut((usize, usize)) -> S) -> Vec<Vec<S>>
+    where
+        S: Clone,
+    {
+        convert::to_dense(
+            self.automaton
+                .cells()
+                .iter()
+                .map(|(&pos, cell)| (pos, cell.clone())),
+            self.grid_size,
+            dead,
         )
     }
-}
 
-impl Default for FrontendBuilder {
-    fn default() -> Self {
-        Self {
-            window_size: (1024, 768),
-            cell_margin: 5,
-            update_rate: Duration::from_millis(100),
-            init_grid_size: (10, 10),
-            default_color: Color::BLACK
+    /// Places a cell at the given position, returning the one that was
+    /// there before.
+    ///
+    /// Positions outside of the displayed grid are fine, though the
+    /// cells there aren't shown.
+    pub fn insert_cell(&mut self, pos: (usize, usize), cell: S) -> Option<S> {
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+        self.cancel_steps();
+        self.automaton.cells_mut().insert(pos, cell)
+    }
+
+    /// Places a pattern in the middle of the grid, making the alive
+    /// cells with `cell`.
+    ///
+    /// Returns the number of cells placed.
+    pub fn place_centered(&mut self, pattern: &Pattern, cell: impl FnMut() -> S) -> usize {
+        let (width, height) = self.grid_size;
+        let origin = (
+            width
+                .saturating_sub(pattern.width)
+                .checked_div(2)
+                .unwrap_or(0),
+            height
+                .saturating_sub(pattern.height)
+                .checked_div(2)
+                .unwrap_or(0),
+        );
+
+        self.apply_loaded(pattern, origin, cell)
+    }
+
+    /// Places the given cells relative to `origin`, overwriting the
+    /// ones there.
+    ///
+    /// Cells whose position would overflow are left out.  Returns the
+    /// number of cells placed.
+    pub fn apply_pattern(
+        &mut self,
+        (origin_x, origin_y): (usize, usize),
+        cells: impl IntoIterator<Item = ((usize, usize), S)>,
+    ) -> usize {
+        self.cancel_steps();
+        let grid = self.automaton.cells_mut();
+        let placed = cells
+            .into_iter()
+            .filter_map(|((x, y), cell)| {
+                grid.insert((origin_x.checked_add(x)?, origin_y.checked_add(y)?), cell);
+                Some(())
+            })
+            .count();
+
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+
+        placed
+    }
+
+    /// Loads an image file (e.g. PNG or BMP) and replaces all the cells
+    /// with the ones `mapper` makes from the colors of the
+    /// corresponding pixels, leaving the positions for which [`None`]
+    /// is returned empty.
+    ///
+    /// The image is stretched to fit the grid size (see
+    /// [`convert::image_to_grid()`]).  [`convert::is_dark()`] can be
+    /// used to pick the cells with a threshold.  Returns the number of
+    /// cells placed, fails if the image can't be loaded or is empty.
+    pub fn load_image_as_cells(
+        &mut self,
+        path: impl AsRef<Path>,
+        mapper: impl FnMut(Color) -> Option<S>,
+    ) -> Result<usize, String> {
+        let colors = convert::image_to_grid(path, self.grid_size)?;
+
+        self.cancel_steps();
+        self.automaton.cells_mut().clear();
+
+        Ok(self.apply_pattern((0, 0), convert::map_colors(colors, mapper)))
+    }
+
+    /// Removes the cells within the given ranges of columns and rows.
+    ///
+    /// The ranges are clipped to the grid size (see
+    /// [`set_grid_size()`](Self::set_grid_size())).
+    pub fn clear_region(&mut self, xs: Range<usize>, ys: Range<usize>) {
+        self.randomize_region(xs, ys, |_| None);
+    }
+
+    /// Replaces the cells within the given ranges of columns and rows
+    /// with the ones made by `f` from their positions (e.g. randomly),
+    /// where [`None`] leaves the position empty.
+    ///
+    /// The ranges are clipped to the grid size (see
+    /// [`set_grid_size()`](Self::set_grid_size())).
+    pub fn randomize_region(
+        &mut self,
+        xs: Range<usize>,
+        ys: Range<usize>,
+        mut f: impl FnMut((usize, usize)) -> Option<S>,
+    ) {
+        let (width, height) = self.grid_size;
+        let xs = xs.start.min(width)..xs.end.min(width);
+        let ys = ys.start.min(height)..ys.end.min(height);
+
+        if xs.is_empty() || ys.is_empty() {
+            return;
+        }
+
+        self.cancel_steps();
+        let cells = self.automaton.cells_mut();
+
+        xs.for_each(|x| {
+            ys.clone().for_each(|y| match f((x, y)) {
+                Some(cell) => {
+                    cells.insert((x, y), cell);
+                }
+                None => {
+                    cells.remove(&(x, y));
+                }
+            });
+        });
+
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+    }
+
+    /// Parses a pattern in the RLE format (see
+    /// [`patterns::parse_rle()`]) and places it with its top-left
+    /// corner at `origin`, making the alive cells with `cell`.
+    ///
+    /// Returns the number of cells placed.
+    pub fn load_rle_at(
+        &mut self,
+        text: &str,
+        origin: (usize, usize),
+        cell: impl FnMut() -> S,
+    ) -> Result<usize, RleError> {
+        let pattern = patterns::parse_rle(text)?;
+
+        Ok(self.apply_loaded(&pattern, origin, cell))
+    }
+
+    /// Like [`load_rle_at()`](Self::load_rle_at()), but reads the
+    /// pattern from a file.
+    pub fn load_rle_file_at(
+        &mut self,
+        path: impl AsRef<Path>,
+        origin: (usize, usize),
+        cell: impl FnMut() -> S,
+    ) -> Result<usize, RleError> {
+        let pattern = patterns::load_rle(path)?;
+
+        Ok(self.apply_loaded(&pattern, origin, cell))
+    }
+
+    /// Reads a pattern file (see [`patterns::load_pattern()`]), in the
+    /// format given by its extension, and places it with its top-left
+    /// corner at `origin`, making the alive cells with `cell`.
+    ///
+    /// Returns the number of cells placed.
+    pub fn load_pattern_at(
+        &mut self,
+        path: impl AsRef<Path>,
+        origin: (usize, usize),
+        cell: impl FnMut() -> S,
+    ) -> Result<usize, RleError> {
+        let pattern = patterns::load_pattern(path)?;
+
+        Ok(self.apply_loaded(&pattern, origin, cell))
+    }
+
+    /// Parses a pattern in the plaintext format (see
+    /// [`patterns::parse_plaintext()`]) and places it like
+    /// [`load_rle_at()`](Self::load_rle_at()).
+    pub fn load_plaintext_at(
+        &mut self,
+        text: &str,
+        origin: (usize, usize),
+        cell: impl FnMut() -> S,
+    ) -> Result<usize, RleError> {
+        let pattern = patterns::parse_plaintext(text)?;
+
+        Ok(self.apply_loaded(&pattern, origin, cell))
+    }
+
+    /// Parses a pattern in the Life 1.06 format (see
+    /// [`patterns::parse_life106()`]) and places it like
+    /// [`load_rle_at()`](Self::load_rle_at()).
+    ///
+    /// Cells with negative coordinates are moved into the grid along
+    /// with the rest of the pattern, `origin` is where the top-left
+    /// corner ends up.
+    pub fn load_life106_at(
+        &mut self,
+        text: &str,
+        origin: (usize, usize),
+        cell: impl FnMut() -> S,
+    ) -> Result<usize, RleError> {
+        let pattern = patterns::parse_life106(text)?;
+
+        Ok(self.apply_loaded(&pattern, origin, cell))
+    }
+
+    /// Encodes the cells in the RLE format (see [`patterns::to_rle()`]),
+    /// cropped to their bounding box.
+    pub fn export_rle(&self) -> String {
+        patterns::to_rle(&Pattern::from_cells(self.automaton.cells().keys().copied()))
+    }
+
+    /// Like [`export_rle()`](Self::export_rle()), but writes the
+    /// pattern to a file.
+    pub fn export_rle_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.export_rle())
+    }
+
+    /// Encodes the cells in the plaintext format (see
+    /// [`patterns::to_plaintext()`]), cropped to their bounding box.
+    pub fn export_plaintext(&self) -> String {
+        patterns::to_plaintext(&Pattern::from_cells(self.automaton.cells().keys().copied()))
+    }
+
+    /// Like [`export_plaintext()`](Self::export_plaintext()), but
+    /// writes the pattern to a file.
+    pub fn export_plaintext_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.export_plaintext())
+    }
+
+    /// Encodes the cells in the Life 1.06 format (see
+    /// [`patterns::to_life106()`]), cropped to their bounding box.
+    pub fn export_life106(&self) -> String {
+        patterns::to_life106(&Pattern::from_cells(self.automaton.cells().keys().copied()))
+    }
+
+    /// Like [`export_life106()`](Self::export_life106()), but writes
+    /// the pattern to a file.
+    pub fn export_life106_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.export_life106())
+    }
+
+    /// Places the alive cells of a pattern (see
+    /// [`apply_pattern()`](Self::apply_pattern())).
+    fn apply_loaded(
+        &mut self,
+        pattern: &Pattern,
+        origin: (usize, usize),
+        mut cell: impl FnMut() -> S,
+    ) -> usize {
+        self.apply_pattern(origin, pattern.cells.iter().map(|&pos| (pos, cell())))
+    }
+
+    /// Removes the cell at the given position, returning it.
+    pub fn remove_cell(&mut self, pos: (usize, usize)) -> Option<S> {
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+        self.cancel_steps();
+        self.automaton.cells_mut().remove(&pos)
+    }
+
+    /// Removes the cell at the given position, or places the one
+    /// returned by `default` if there's none.
+    ///
+    /// Returns whether there's a cell at the position now.
+    pub fn toggle_cell(&mut self, pos: (usize, usize), default: impl FnOnce() -> S) -> bool {
+        if self.remove_cell(pos).is_some() {
+            false
+        } else {
+            self.insert_cell(pos, default());
+            true
+        }
+    }
+
+    /// Displays another automaton in the same window, returning the
+    /// previous one.
+    ///
+    /// The generation counter is reset (see
+    /// [`reset()`](Self::reset())).
+    pub fn replace_automaton(&mut self, automaton: Automaton<S, D>) -> Automaton<S, D> {
+        self.cancel_steps();
+        let old = mem::replace(&mut self.automaton, automaton);
+
+        self.reset();
+        self.step_stats = None;
+        self.diff = None;
+
+        old
+    }
+
+    /// Closes the window, returning the automaton in its final state.
+    ///
+    /// The window is closed by the time this returns, so another
+    /// frontend can be opened right away.
+    pub fn into_automaton(self) -> Automaton<S, D> {
+        let Self {
+            automaton,
+            canvas,
+            grid_cache,
+            font,
+            rl,
+            ..
+        } = self;
+
+        // NOTE: Textures have to be dropped before the window is closed
+        drop((canvas, grid_cache, font));
+        drop(rl);
+
+        automaton
+    }
+
+    /// Returns the visible cells along with their screen rectangles,
+    /// as drawn by [`display_grid()`](Self::display_grid()).
+    ///
+    /// Cells partially scrolled out of view are included.
+    pub fn cell_rects(&self) -> impl Iterator<Item = ((usize, usize), Rectangle)> {
+        let context = self.overlay_context();
+        let (xs, ys) = self.visible_cells();
+
+        xs.flat_map(move |x| ys.clone().map(move |y| ((x, y), context.cell_rect((x, y)))))
+    }
+
+    /// Returns the position of the cell at the given screen point (in
+    /// logical units, see
+    /// [`mouse_position()`](Self::mouse_position())), or [`None`] if
+    /// there's none (see [`cell_rects()`](Self::cell_rects())).
+    pub fn screen_to_cell(&self, point: Vector2) -> Option<(usize, usize)> {
+        self.overlay_context().cell_at(point)
+    }
+
+    /// Checks if the window should close (e.g. `esc` pressed, or
+    /// [`request_close()`](Self::request_close()) called).
+    ///
+    /// Reaching the generation limit only counts if
+    /// [`FrontendBuilder::close_on_finish()`] is set.
+    pub fn window_should_close(&self) -> bool {
+        self.automaton.is_finished()
+            || self.close_requested()
+            || (self.close_on_finish && self.limit_reached())
+    }
+
+    /// Updates the inner timer and computes as many generations as
+    /// the update rate allows for the time passed since the last call
+    /// (see [`FrontendBuilder::update_rate()`]), but at most
+    /// [`FrontendBuilder::max_steps_per_frame()`].
+    ///
+    /// In turbo mode (see [`set_turbo()`](Self::set_turbo())) the update
+    /// rate is ignored and generations are computed until the time
+    /// budget for the frame is used up.
+    ///
+    /// Returns the state after the last computed generation, or
+    /// [`None`] if none were computed (see
+    /// [`tick_counted()`](Self::tick_counted()) for the number of them).
+    ///
+    /// The timer is paused once the generation limit is reached (see
+    /// [`FrontendBuilder::max_generations()`]).
+    pub fn tick(&mut self) -> Option<ExecutionState> {
+        self.tick_counted().state
+    }
+
+    /// Like [`tick()`](Self::tick()), but also reports how many
+    /// generations were computed.
+    pub fn tick_counted(&mut self) -> TickResult {
+        self.check_focus();
+        self.check_minimized();
+
+        if self.limit_reached() {
+            self.pause();
+            return TickResult::default();
+        }
+
+        if self.shell.stepper.is_some() {
+            return self.tick_threaded();
+        }
+
+        if self.shell.turbo {
+            let start = Instant::now();
+            let budget = self.shell.turbo_budget;
+
+            return self.run_steps(|_| start.elapsed() < budget);
+        }
+
+        let TimerState::Finished(cycles) = self.timer.update(self.max_steps_per_frame) else {
+            return TickResult::default();
+        };
+        let cycles = usize::try_from(cycles).unwrap_or(usize::MAX);
+
+        self.run_steps(|steps| steps < cycles)
+    }
+
+    /// Like [`tick_counted()`](Self::tick_counted()), but leaves computing
+    /// the generations to the worker thread (see
+    /// [`set_threaded()`](Self::set_threaded())), taking in the ones it
+    /// computed since the last call.
+    ///
+    /// In turbo mode the worker computes batches of
+    /// [`FrontendBuilder::max_steps_per_frame()`] generations back to
+    /// back instead.
+    fn tick_threaded(&mut self) -> TickResult {
+        let due = if self.shell.turbo {
+            usize::try_from(self.max_steps_per_frame).unwrap_or(usize::MAX)
+        } else if let TimerState::Finished(cycles) = self.timer.update(self.max_steps_per_frame) {
+            usize::try_from(cycles).unwrap_or(usize::MAX)
+        } else {
+            0
+        };
+        let Some(stepper) = &mut self.shell.stepper else {
+            return TickResult::default();
+        };
+
+        if !self.timer.is_paused() && !self.automaton.is_finished() {
+            stepper.request(&self.automaton, due);
+        }
+
+        let mut steps = mem::take(&mut self.shell.received_steps);
+        let mut result = TickResult::default();
+
+        stepper.receive(&mut steps);
+        for Step {
+            state,
+            result: step_state,
+            duration,
+            ..
+        } in steps.drain(..)
+        {
+            if self.is_paused() || self.automaton.is_finished() {
+                self.cancel_steps();
+                break;
+            }
+
+            result.state = Some(self.advance_with(|automaton| {
+                *automaton = state;
+
+                (step_state, duration)
+            }));
+            result.steps = result.steps.saturating_add(1);
+
+            if self.limit_reached() {
+                self.pause();
+                self.cancel_steps();
+                self.toast(format!("Stopped at generation {}", self.generation));
+                break;
+            }
+        }
+
+        self.shell.received_steps = steps;
+
+        result
+    }
+
+    /// Checks whether the generations are computed on a background
+    /// thread (see [`set_threaded()`](Self::set_threaded())).
+    pub const fn is_threaded(&self) -> bool {
+        self.shell.stepper.is_some()
+    }
+
+    /// Drops the generations being computed on the background thread,
+    /// if any, e.g. because the cells were edited in the meantime.
+    fn cancel_steps(&mut self) {
+        self.shell.cancel_steps();
+    }
+
+    /// Pauses the simulation when the window loses focus and resumes it
+    /// when it's focused again, unless it was already paused (see
+    /// [`FrontendBuilder::pause_on_focus_loss()`]).
+    fn check_focus(&mut self) {
+        if self.headless {
+            return;
+        }
+
+        let focused = self.rl.is_window_focused();
+        let pause = self.shell.check_focus(focused, self.is_paused());
+
+        self.pause_or_resume(pause);
+    }
+
+    /// Pauses (`Some(true)`) or resumes (`Some(false)`) the simulation,
+    /// or leaves it as is.
+    fn pause_or_resume(&mut self, pause: Option<bool>) {
+        match pause {
+            Some(true) => self.pause(),
+            Some(false) => self.resume(),
+            None => (),
+        }
+    }
+
+    /// Pauses the simulation while the window is minimized or hidden and
+    /// resumes it when it's restored, unless it was already paused (see
+    /// [`FrontendBuilder::simulate_when_minimized()`]).
+    fn check_minimized(&mut self) {
+        let minimized = self.is_minimized();
+        let pause = self.shell.check_minimized(minimized, self.is_paused());
+
+        self.pause_or_resume(pause);
+    }
+
+    /// Checks whether the window is minimized or hidden, in which case
+    /// [`display_grid()`](Self::display_grid()) doesn't draw anything.
+    pub fn is_minimized(&self) -> bool {
+        !self.headless && (self.rl.is_window_minimized() || self.rl.is_window_hidden())
+    }
+
+    /// Hides the window for good (see [`FrontendBuilder::headless()`]).
+    fn hide_window(&mut self) {
+        let state = self.rl.get_window_state().set_window_hidden(true);
+
+        self.headless = true;
+        self.rl.set_window_state(state);
+        self.invalidate_layout();
+    }
+
+    /// Checks whether the window is hidden for good (see
+    /// [`FrontendBuilder::headless()`]).
+    pub const fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Makes [`window_should_close()`](Self::window_should_close())
+    /// return `true` from now on, e.g. to end the main loop of a
+    /// headless frontend (see [`FrontendBuilder::headless()`]).
+    pub fn request_close(&mut self) {
+        self.close_requested = true;
+    }
+
+    /// Checks whether closing the window was requested, either by the
+    /// user or with [`request_close()`](Self::request_close()).
+    fn close_requested(&self) -> bool {
+        self.close_requested || (!self.headless && self.rl.window_should_close())
+    }
+
+    /// Returns the time a frame should take at least, if frames are
+    /// paced (see [`FrontendBuilder::frame_pacing()`]).
+    pub const fn frame_pacing(&self) -> Option<Duration> {
+        self.pacer.target
+    }
+
+    /// Sets the time a frame should take at least, or [`None`] to draw
+    /// frames as fast as possible (see
+    /// [`FrontendBuilder::frame_pacing()`]).
+    pub fn set_frame_pacing(&mut self, frame_time: Option<Duration>) {
+        self.pacer.target = frame_time;
+    }
+
+    /// Sleeps until the frame took the time it should (see
+    /// [`set_frame_pacing()`](Self::set_frame_pacing())), waking up in
+    /// time for the next step.
+    fn pace_frame(&mut self) {
+        let mouse_used = self.rl.get_mouse_delta().length_sqr() > 0.
+            || self.rl.get_mouse_wheel_move().abs() > 0.
+            || [
+                MouseButton::MOUSE_BUTTON_LEFT,
+                MouseButton::MOUSE_BUTTON_RIGHT,
+                MouseButton::MOUSE_BUTTON_MIDDLE,
+            ]
+            .into_iter()
+            .any(|button| self.rl.is_mouse_button_down(button));
+        // NOTE: Vsync paces the frames already, turbo mode fills them
+        // with steps, and headless frontends run as fast as they can
+        let skip = self.shell.turbo
+            || self.headless
+            || mouse_used
+            || self.rl.get_window_state().vsync_hint();
+
+        self.pacer.end_frame(self.timer.until_next_cycle(), skip);
+    }
+
+    /// Computes generations as long as `keep_going` allows (given the
+    /// number of steps made so far), stopping early if the automaton
+    /// finishes or the simulation gets paused.
+    fn run_steps(&mut self, mut keep_going: impl FnMut(usize) -> bool) -> TickResult {
+        let mut result = TickResult::default();
+
+        // NOTE: The timer may get paused by a step, e.g. if the automaton
+        // has stabilized
+        while keep_going(result.steps) && !self.is_paused() && !self.automaton.is_finished() {
+            result.state = Some(self.advance());
+            result.steps = result.steps.saturating_add(1);
+
+            if self.limit_reached() {
+                self.pause();
+                self.toast(format!("Stopped at generation {}", self.generation));
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Renders the grid only every `render_every` generations (`0` and
+    /// `1` render every generation).
+    ///
+    /// In between, [`display_grid()`](Self::display_grid()) shows the
+    /// grid as it was last rendered, which is much cheaper in pixel mode
+    /// (see [`FrontendBuilder::pixel_mode()`]), as the texture is reused
+    /// as is.  The HUD, input handling and [`tick()`](Self::tick()) are
+    /// not affected.
+    pub fn set_render_every(&mut self, render_every: usize) {
+        self.snapshot.every = render_every.max(1);
+        self.snapshot.invalidate();
+    }
+
+    /// Checks whether turbo mode is on.
+    pub const fn turbo(&self) -> bool {
+        self.shell.turbo
+    }
+
+    /// Turns turbo mode on or off.
+    ///
+    /// In turbo mode, [`tick()`](Self::tick()) computes as many
+    /// generations as fit into the time budget of a frame (see
+    /// [`FrontendBuilder::turbo_budget()`]), while the screen is still
+    /// refreshed at the display rate.  The simulation is unpaused when
+    /// it's turned on, and the pause state is restored when it's turned
+    /// off.
+    pub fn set_turbo(&mut self, turbo: bool) {
+        if turbo == self.shell.turbo {
+            return;
+        }
+
+        if turbo {
+            self.shell.paused_before_turbo = self.is_paused();
+            self.resume();
+        } else {
+            self.timer.set_paused(self.shell.paused_before_turbo);
+            // NOTE: Otherwise the time spent in turbo mode would be caught
+            // up with
+            self.timer.update(0);
+        }
+
+        self.shell.turbo = turbo;
+        self.toast(if turbo { "Turbo on" } else { "Turbo off" });
+    }
+
+    /// Turns turbo mode on or off, depending on its current state.
+    pub fn toggle_turbo(&mut self) {
+        self.set_turbo(!self.shell.turbo);
+    }
+
+    /// Gradually changes the time between generations to `target` over
+    /// the given (wall-clock) time.
+    ///
+    /// The change is frozen while paused, and cancelled by
+    /// [`set_update_rate()`](Self::set_update_rate()).
+    pub fn ramp_rate_to(&mut self, target: Duration, over: Duration) {
+        self.timer.ramp_to(target, over);
+    }
+
+    /// Returns how far along the wait for the next generation is, from
+    /// `0` to `1`, as of the last [`tick()`](Self::tick()).
+    ///
+    /// Useful for animating transitions between generations.
+    pub fn step_progress(&self) -> f32 {
+        self.timer.progress()
+    }
+
+    /// Checks whether the simulation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.timer.is_paused()
+    }
+
+    /// Pauses the simulation, so that [`tick()`](Self::tick()) stops
+    /// computing generations.
+    pub fn pause(&mut self) {
+        self.timer.pause();
+    }
+
+    /// Resumes the simulation (see [`pause()`](Self::pause())).
+    pub fn resume(&mut self) {
+        self.timer.resume();
+    }
+
+    /// Pauses or resumes the simulation, depending on its current state.
+    pub fn toggle_pause(&mut self) {
+        self.timer.toggle_pause();
+    }
+
+    /// Returns the time between generations (see
+    /// [`FrontendBuilder::update_rate()`]).
+    pub fn update_rate(&self) -> Duration {
+        self.timer.rate()
+    }
+
+    /// Sets the time between generations, keeping the progress
+    /// towards the next one.
+    pub fn set_update_rate(&mut self, update_rate: Duration) {
+        self.timer.set_rate(update_rate);
+    }
+
+    /// Returns the number of generations computed per second by
+    /// [`tick()`](Self::tick()), or `0` while paused.
+    ///
+    /// It's infinite if a generation is computed every frame.
+    pub fn steps_per_second(&self) -> f32 {
+        if self.is_paused() {
+            0.
+        } else {
+            frequency_from_period(self.timer.rate())
+        }
+    }
+
+    /// Sets the number of generations computed per second by
+    /// [`tick()`](Self::tick()).
+    ///
+    /// `0` pauses the simulation, any other value resumes it.  Rates
+    /// higher than the frame rate (e.g. [`f32::INFINITY`]) compute a
+    /// generation every frame.
+    pub fn set_steps_per_second(&mut self, steps_per_second: f32) {
+        match period_from_frequency(steps_per_second) {
+            Some(update_rate) => {
+                self.set_update_rate(update_rate);
+                self.resume();
+            }
+            None => self.pause(),
+        }
+    }
+
+    /// Computes the next generation of the automaton immediately.
+    ///
+    /// See [`tick()`](Self::tick()) for properly timed updating.
+    pub fn step(&mut self) -> ExecutionState {
+        self.advance()
+    }
+
+    /// Computes generations immediately until `until` returns `true`
+    /// for the generation number and the automaton, the generation
+    /// limit is reached (see [`FrontendBuilder::max_generations()`]) or
+    /// the automaton finishes, returning which one it was.
+    ///
+    /// Nothing is drawn in the meantime, see
+    /// [`step_until_displaying()`](Self::step_until_displaying()) to
+    /// keep the window responsive.
+    pub fn step_until<F>(&mut self, until: F) -> StopReason
+    where
+        F: FnMut(u64, &Automaton<S, D>) -> bool,
+    {
+        self.run_until(until, |_, _| false)
+    }
+
+    /// Like [`step_until()`](Self::step_until()), but displays the grid
+    /// every `every` generations, which also handles the input.
+    ///
+    /// Stops early if the window should close.
+    pub fn step_until_displaying<C, F>(&mut self, until: F, every: usize) -> StopReason
+    where
+        S: RenderCell<C> + MaybeSync,
+        C: Into<Color>,
+        F: FnMut(u64, &Automaton<S, D>) -> bool,
+    {
+        let every = every.max(1);
+
+        self.run_until(until, |frontend, steps| {
+            if steps.checked_rem(every) != Some(0) {
+                return false;
+            }
+
+            frontend.display_grid::<C>();
+            frontend.close_requested()
+        })
+    }
+
+    /// Computes generations until one of the conditions of
+    /// [`step_until()`](Self::step_until()) is met, calling `between`
+    /// with the number of steps made after each one, which stops it by
+    /// returning `true`.
+    fn run_until<F, G>(&mut self, mut until: F, mut between: G) -> StopReason
+    where
+        F: FnMut(u64, &Automaton<S, D>) -> bool,
+        G: FnMut(&mut Self, usize) -> bool,
+    {
+        let mut steps = 0usize;
+
+        loop {
+            if until(self.generation, &self.automaton) {
+                return StopReason::Predicate;
+            }
+            if self.limit_reached() {
+                return StopReason::GenerationLimit;
+            }
+            if self.automaton.is_finished() {
+                return StopReason::Finished;
+            }
+
+            self.advance();
+            steps = steps.saturating_add(1);
+
+            if between(self, steps) {
+                return StopReason::WindowClosed;
+            }
+        }
+    }
+
+    /// Computes the next `n` generations immediately, stopping early if
+    /// the automaton finishes or the generation limit is reached (see
+    /// [`set_max_generations()`](Self::set_max_generations())), which
+    /// pauses the simulation like [`tick()`](Self::tick()) does.
+    ///
+    /// Returns the state after the last step, or [`None`] if no steps
+    /// were made.
+    pub fn step_n(&mut self, n: usize) -> Option<ExecutionState> {
+        let mut state = None;
+
+        for _ in 0..n {
+            if self.automaton.is_finished() || self.limit_reached() {
+                break;
+            }
+
+            state = Some(self.advance());
+        }
+
+        if self.limit_reached() {
+            self.pause();
+        }
+
+        state
+    }
+
+    /// Returns the number of generations computed so far.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the generation the simulation stops at, if any.
+    pub const fn max_generations(&self) -> Option<u64> {
+        self.max_generations
+    }
+
+    /// Sets the generation the simulation stops at, or removes the
+    /// limit if [`None`] is given.
+    ///
+    /// Once it's reached, [`tick()`](Self::tick()) doesn't compute any
+    /// more generations and the timer is paused.  Raising or removing
+    /// the limit, or resetting the generation counter (see
+    /// [`reset()`](Self::reset())), lets the simulation continue.
+    pub fn set_max_generations(&mut self, max_generations: Option<u64>) {
+        self.max_generations = max_generations;
+    }
+
+    /// Checks whether the generation limit is reached.
+    fn limit_reached(&self) -> bool {
+        self.max_generations
+            .is_some_and(|limit| self.generation >= limit)
+    }
+
+    /// Resets the generation counter to `0`, leaving the cells as they
+    /// are, e.g. to count the generations from an edited state.
+    ///
+    /// This also clears the graph, the history and the states
+    /// remembered to detect cycles, as they refer to the previous
+    /// generations.  To start over from another state, replace the
+    /// automaton instead (see
+    /// [`replace_automaton()`](Self::replace_automaton())), which resets
+    /// the counter as well.
+    pub fn reset(&mut self) {
+        self.restart_from(0);
+    }
+
+    /// Sets the generation counter, clearing everything that refers to
+    /// the previous generations (see
+    /// [`reset()`](Self::reset())).
+    fn restart_from(&mut self, generation: u64) {
+        self.generation = generation;
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+        self.clear_graph();
+        if let Some(detector) = &mut self.cycle_detector {
+            detector.clear();
+        }
+        if let Some(history) = &mut self.shell.history {
+            history.clear();
+            history.record(&self.automaton, self.generation);
+        }
+    }
+
+    /// Shows the previous generation kept in the history, pausing the
+    /// simulation (see [`set_history_length()`](Self::set_history_length())).
+    ///
+    /// Returns whether there was one.
+    pub fn step_back(&mut self) -> bool {
+        let Some((automaton, generation)) = self.shell.history.as_mut().and_then(History::back)
+        else {
+            return false;
+        };
+
+        self.pause();
+        self.show_past(automaton, generation);
+
+        true
+    }
+
+    /// Shows the next generation kept in the history, or resumes the
+    /// simulation if the last one is already shown (see
+    /// [`step_back()`](Self::step_back())).
+    pub fn step_forward(&mut self) {
+        match self.shell.history.as_mut().and_then(History::forward) {
+            Some((automaton, generation)) => self.show_past(automaton, generation),
+            None => self.resume(),
+        }
+    }
+
+    /// Replaces the automaton with a state from the history, rewinding
+    /// the statistics along with it.
+    fn show_past(&mut self, automaton: Automaton<S, D>, generation: u64) {
+        self.cancel_steps();
+        self.automaton = automaton;
+        self.generation = generation;
+        self.snapshot.invalidate();
+        self.live_bounds.set(None);
+        if let Some(graph) = &mut self.graph {
+            graph.truncate_after(generation);
+        }
+        if let Some(detector) = &mut self.cycle_detector {
+            detector.clear();
+        }
+        self.step_stats = None;
+        self.diff = None;
+        self.update_title();
+    }
+
+    /// Pauses the simulation once the automaton repeats one of the
+    /// last `max_period` states, or disables it if `0` is given (see
+    /// [`FrontendBuilder::pause_on_period()`]).
+    pub fn set_pause_on_period(&mut self, max_period: usize) {
+        self.set_detect_period(max_period);
+        self.pause_on_cycle = true;
+    }
+
+    /// Watches for the automaton repeating one of the last `max_period`
+    /// states without pausing (see
+    /// [`set_on_cycle()`](Self::set_on_cycle())), or stops watching if
+    /// `0` is given.
+    pub fn set_detect_period(&mut self, max_period: usize) {
+        self.cycle_detector = (max_period > 0).then(|| CycleDetector::new(max_period));
+        self.pause_on_cycle = false;
+    }
+
+    /// Sets a callback called with the period and the generation once
+    /// the automaton starts repeating itself (see
+    /// [`set_detect_period()`](Self::set_detect_period())), or removes
+    /// it if [`None`] is given.
+    pub fn set_on_cycle(&mut self, on_cycle: Option<Box<dyn FnMut(usize, u64)>>) {
+        self.on_cycle = on_cycle;
+    }
+
+    /// Sets a callback called after each computed generation with its
+    /// number and the automaton, or removes it if [`None`] is given.
+    ///
+    /// It's called once per generation, even if several of them are
+    /// computed by a single [`tick()`](Self::tick()).
+    pub fn set_on_step(&mut self, on_step: Option<Box<dyn FnMut(u64, &Automaton<S, D>)>>) {
+        self.on_step = on_step;
+    }
+
+    /// Sets a callback called once the simulation is over, or removes
+    /// it if [`None`] is given.
+    ///
+    /// That's when the automaton finishes, the generation limit is
+    /// reached (see [`FrontendBuilder::max_generations()`]), or the
+    /// automaton starts repeating itself (see
+    /// [`FrontendBuilder::pause_on_period()`]), whichever comes first.
+    /// It's called only once, before the window is closed.
+    pub fn set_on_finish(&mut self, on_finish: Option<Box<dyn FnOnce(&Automaton<S, D>)>>) {
+        self.on_finish = on_finish;
+    }
+
+    /// Calls the callback set with [`set_on_finish()`](Self::set_on_finish()),
+    /// unless it was already called.
+    fn fire_on_finish(&mut self) {
+        if let Some(on_finish) = self.on_finish.take() {
+            on_finish(&self.automaton);
+        }
+    }
+
+    /// Returns the cells that came alive or died in the last
+    /// generation, if it's tracked (see
+    /// [`FrontendBuilder::track_diff()`]).
+    pub const fn last_diff(&self) -> Option<&GenerationDiff> {
+        self.diff.as_ref()
+    }
+
+    /// Sets the statistic shown by the graph (see
+    /// [`FrontendBuilder::show_graph()`]), clearing it.
+    ///
+    /// By default it's the series set with
+    /// [`set_graph_series()`](Self::set_graph_series()).
+    pub fn set_graph_source<F>(&mut self, source: F)
+    where
+        F: Fn(&Automaton<S, D>) -> f32 + 'static,
+    {
+        self.graph_source = Some(Box::new(source));
+        self.clear_graph();
+    }
+
+    /// Sets the statistic shown by the graph, clearing it.
+    ///
+    /// It's only used if no custom source was set with
+    /// [`set_graph_source()`](Self::set_graph_source()).
+    pub fn set_graph_series(&mut self, graph_series: GraphSeries) {
+        self.graph_series = graph_series;
+        self.clear_graph();
+    }
+
+    /// Returns the number of cells born and died in the last step, if
+    /// they're tracked (see [`FrontendBuilder::track_step_stats()`]).
+    pub const fn last_step_stats(&self) -> Option<StepStats> {
+        self.step_stats
+    }
+
+    /// Returns the generation of the last successful autosave, if any.
+    pub const fn last_autosave(&self) -> Option<u64> {
+        self.shell.last_autosave
+    }
+
+    /// Saves the state if an autosave is due, disabling autosaving if
+    /// that fails.
+    #[cfg(feature = "serde")]
+    fn run_autosave(&mut self) {
+        let Some(mut autosave) = self.shell.autosave.take() else {
+            return;
+        };
+
+        if !autosave.is_due(self.generation) {
+            self.shell.autosave = Some(autosave);
+            return;
+        }
+
+        match autosave.save(self, self.generation) {
+            Ok(()) => {
+                self.shell.last_autosave = Some(self.generation);
+                self.shell.autosave = Some(autosave);
+            }
+            Err(error) => self.toast(format!("Autosave failed, disabling it: {error}")),
+        }
+    }
+
+    /// Adds a column to the statistics log (see
+    /// [`start_stats_log()`](Self::start_stats_log())), computed from
+    /// the automaton after each generation.
+    ///
+    /// Metrics added while logging are only logged after restarting it.
+    pub fn add_stats_metric<F>(&mut self, name: impl Into<String>, metric: F)
+    where
+        F: Fn(&Automaton<S, D>) -> f32 + 'static,
+    {
+        self.shell
+            .stats_metrics
+            .push((name.into(), Box::new(metric)));
+    }
+
+    /// Starts writing a CSV row per generation to a file, with the
+    /// generation, the population, the births, the deaths and the
+    /// metrics added with [`add_stats_metric()`](Self::add_stats_metric()).
+    ///
+    /// The file is either appended to or truncated, and gets a header
+    /// if it's empty.  Rows are flushed every few generations and when
+    /// logging stops (see [`stop_stats_log()`](Self::stop_stats_log())).
+    /// Fails if the file can't be opened.
+    pub fn start_stats_log(&mut self, path: impl AsRef<Path>, append: bool) -> io::Result<()> {
+        self.shell.start_stats_log(path.as_ref(), append)
+    }
+
+    /// Stops logging the statistics, writing out the remaining rows.
+    pub fn stop_stats_log(&mut self) {
+        self.shell.stop_stats_log();
+    }
+
+    /// Writes the row of the current generation to the statistics log,
+    /// stopping it if that fails.
+    fn log_stats(&mut self) {
+        let Some(log) = &mut self.shell.stats_log else {
+            return;
+        };
+        let metrics = self
+            .stats_metrics
+            .iter()
+            .take(log.metrics())
+            .map(|(_, metric)| metric(&self.automaton));
+
+        if let Err(error) = log.write_row(
+            self.generation,
+            Some(self.population()),
+            self.step_stats,
+            metrics,
+        ) {
+            self.shell.stats_log = None;
+            self.toast(format!("Statistics log failed: {error}"));
+        }
+    }
+
+    /// Checks whether births and deaths have to be tracked, either
+    /// explicitly or for the status bar or the graph.
+    fn tracks_step_stats(&self) -> bool {
+        self.track_step_stats
+            || self.track_diff
+            || self.shell.stats_log.is_some()
+            || self
+                .status_bar
+                .as_ref()
+                .is_some_and(StatusBar::needs_step_stats)
+            || (self.graph.is_some()
+                && self.graph_source.is_none()
+                && self.graph_series.needs_step_stats())
+    }
+
+    /// Removes all the samples from the graph.
+    pub fn clear_graph(&mut self) {
+        if let Some(graph) = &mut self.graph {
+            graph.clear();
+        }
+    }
+
+    /// Returns the number of live cells.
+    pub fn population(&self) -> usize {
+        self.automaton.cells().len()
+    }
+
+    /// Steps the automaton, keeping track of the generations.
+    fn advance(&mut self) -> ExecutionState {
+        self.cancel_steps();
+        self.advance_with(|automaton| {
+            let start = Instant::now();
+            let state = automaton.step();
+
+            (state, start.elapsed())
+        })
+    }
+
+    /// Like [`advance()`](Self::advance()), but steps the automaton
+    /// with `step`, which also returns how long the step took.
+    fn advance_with(
+        &mut self,
+        step: impl FnOnce(&mut Automaton<S, D>) -> (ExecutionState, Duration),
+    ) -> ExecutionState {
+        // NOTE: The initial state has to be remembered as well
+        if self
+            .cycle_detector
+            .as_ref()
+            .is_some_and(CycleDetector::is_empty)
+        {
+            self.detect_cycle();
+        }
+        // NOTE: The cells are only compared if something needs it
+        let before: Option<FxHashSet<_>> = self
+            .tracks_step_stats()
+            .then(|| self.automaton.cells().keys().copied().collect());
+        let (state, step_time) = step(&mut self.automaton);
+        self.live_bounds.set(None);
+        self.frame_timer.record_step(step_time);
+        self.generation = self.generation.saturating_add(1);
+        self.shell.rate_meter.record();
+        self.step_stats = before.as_ref().map(|before| {
+            let cells = self.automaton.cells();
+            let births = cells.keys().filter(|pos| !before.contains(pos)).count();
+
+            StepStats {
+                births,
+                deaths: before
+                    .len()
+                    .saturating_sub(cells.len().saturating_sub(births)),
+            }
+        });
+        if let (true, Some(before)) = (self.track_diff, &before) {
+            let cells = self.automaton.cells();
+            let mut diff = self.diff.take().unwrap_or_default();
+
+            diff.clear();
+            diff.born
+                .extend(cells.keys().filter(|pos| !before.contains(pos)));
+            diff.died
+                .extend(before.iter().filter(|pos| !cells.contains_key(pos)));
+            self.diff = Some(diff);
+        }
+        if let Some(graph) = &mut self.graph {
+            let value = self.graph_source.as_ref().map_or_else(
+                || {
+                    self.graph_series
+                        .sample(Some(self.population()), self.step_stats)
+                },
+                |source| Some(source(&self.automaton)),
+            );
+
+            if let Some(value) = value {
+                graph.push(self.generation, value);
+            }
+        }
+        self.detect_cycle();
+        if let Some(history) = &mut self.shell.history {
+            history.record(&self.automaton, self.generation);
+        }
+        // NOTE: Only updated on steps to avoid setting it every frame
+        if self.shell.title_format.is_some() {
+            self.update_title();
+        }
+        self.log_stats();
+        #[cfg(feature = "serde")]
+        self.run_autosave();
+        // NOTE: Called last, so that the frontend is up to date even if
+        // it panics
+        if let Some(on_step) = &mut self.on_step {
+            on_step(self.generation, &self.automaton);
+        }
+        if self.automaton.is_finished() || self.limit_reached() {
+            self.fire_on_finish();
+        }
+
+        state
+    }
+
+    /// Reports the automaton stopping to change or starting to repeat
+    /// itself (see [`FrontendBuilder::detect_period()`]).
+    fn detect_cycle(&mut self) {
+        if self.cycle_detector.is_none() {
+            return;
+        }
+
+        let cells: FxHashSet<_> = self.automaton.cells().keys().copied().collect();
+        // NOTE: The hashes of the positions are summed, as the order of
+        // the cells in the map is arbitrary
+        let hash = cells
+            .iter()
+            .map(|pos| FxBuildHasher.hash_one(pos))
+            .fold(0, u64::wrapping_add);
+        let Some(period) = self
+            .cycle_detector
+            .as_mut()
+            .and_then(|detector| detector.push(hash, cells))
+        else {
+            return;
+        };
+
+        if let Some(on_cycle) = &mut self.on_cycle {
+            on_cycle(period, self.generation);
+        }
+        if !self.pause_on_cycle {
+            return;
+        }
+
+        self.pause();
+        self.toast(if period == 1 {
+            format!("Stable at generation {}", self.generation)
+        } else {
+            format!("Period {period} at generation {}", self.generation)
+        });
+        self.fire_on_finish();
+    }
+
+    /// Shows the current update rate in a toast.
+    fn toast_update_rate(&mut self) {
+        self.toast(format!("Speed: {}", format_update_rate(self.timer.rate())));
+    }
+
+    /// Copies the cells within the given ranges of columns and rows to
+    /// the clipboard in the RLE format (see [`patterns::to_rle()`]),
+    /// cropped to their bounding box, e.g. to paste them into Golly.
+    ///
+    /// Returns whether anything was copied, a toast tells why not.
+    pub fn copy_region_to_clipboard(&mut self, xs: Range<usize>, ys: Range<usize>) -> bool {
+        let cells = self
+            .automaton
+            .cells()
+            .keys()
+            .copied()
+            .filter(|(x, y)| xs.contains(x) && ys.contains(y));
+
+        self.copy_pattern(&Pattern::from_cells(cells))
+    }
+
+    /// Copies a pattern to the clipboard in the RLE format, showing a
+    /// toast with the outcome.
+    fn copy_pattern(&mut self, pattern: &Pattern) -> bool {
+        if pattern.cells.is_empty() {
+            self.toast("Nothing to copy");
+            return false;
+        }
+
+        let copied = self
+            .clipboard
+            .set_text(&mut self.rl, &patterns::to_rle(pattern));
+
+        if copied {
+            self.toast(format!("Copied {} cells", pattern.cells.len()));
+        } else {
+            self.toast("Copying failed");
+        }
+
+        copied
+    }
+
+    /// Selects the cells between two corners (inclusive), e.g. to copy
+    /// them with
+    /// [`copy_selection_to_clipboard()`](Self::copy_selection_to_clipboard()).
+    ///
+    /// The cells can also be selected by dragging with Shift+LMB (see
+    /// [`default_key_actions()`](Self::default_key_actions())).
+    pub fn select(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize)) {
+        self.selection = Some(((x0.min(x1), y0.min(y1)), (x0.max(x1), y0.max(y1))));
+        self.update_exit_key();
+    }
+
+    /// Returns the smallest and the largest coordinates of the selected
+    /// cells (see [`select()`](Self::select())).
+    pub const fn selection(&self) -> Option<Bounds> {
+        self.selection
+    }
+
+    /// Deselects the cells (see [`select()`](Self::select())).
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+        self.selection_anchor = None;
+        self.update_exit_key();
+    }
+
+    /// Copies the selected cells (see [`select()`](Self::select())) to
+    /// the clipboard, like
+    /// [`copy_region_to_clipboard()`](Self::copy_region_to_clipboard()).
+    ///
+    /// Returns whether anything was copied, a toast tells why not (e.g.
+    /// nothing is selected, or there are no cells in the selection).
+    pub fn copy_selection_to_clipboard(&mut self) -> bool {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.selection else {
+            self.toast("Nothing selected (drag with Shift+LMB to select)");
+            return false;
+        };
+
+        self.copy_region_to_clipboard(
+            min_x..max_x.saturating_add(1),
+            min_y..max_y.saturating_add(1),
+        )
+    }
+
+    /// Replaces the clipboard patterns are copied to and pasted from
+    /// (the system one by default), e.g. with a
+    /// [`MemoryClipboard`](crate::clipboard::MemoryClipboard).
+    pub fn set_clipboard(&mut self, clipboard: impl Clipboard + 'static) {
+        self.shell.clipboard = Box::new(clipboard);
+    }
+
+    /// Returns the text on the clipboard (see
+    /// [`set_clipboard()`](Self::set_clipboard())), if it holds any.
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        self.shell.clipboard.text(&self.rl)
+    }
+
+    /// Sets the function that makes the cells of pasted patterns (see
+    /// [`paste_from_clipboard()`](Self::paste_from_clipboard())).
+    pub fn set_paste_cell<F: Fn() -> S + 'static>(&mut self, paste_cell: F) {
+        self.paste_cell = Some(Box::new(paste_cell));
+    }
+
+    /// Reads a pattern from the clipboard, in the RLE or the plaintext
+    /// format, to be placed with a click at the cell under the cursor
+    /// or canceled with Escape (see
+    /// [`default_key_actions()`](Self::default_key_actions())).
+    ///
+    /// The cells are made by the function set with
+    /// [`set_paste_cell()`](Self::set_paste_cell()).  Returns whether a
+    /// pattern was read, a toast tells why not.
+    pub fn paste_from_clipboard(&mut self) -> bool {
+        if self.paste_cell.is_none() {
+            self.toast("Pasting needs `set_paste_cell()`");
+            return false;
+        }
+
+        let Some(text) = self.shell.clipboard.text(&self.rl) else {
+            self.toast("The clipboard doesn't hold text");
+            return false;
+        };
+        let pattern = match patterns::parse_rle(&text) {
+            Ok(pattern) => pattern,
+            Err(error) => match patterns::parse_plaintext(&text) {
+                Ok(pattern) if !pattern.cells.is_empty() => pattern,
+                _ => {
+                    self.toast(format!("Invalid pattern: {error}"));
+                    return false;
+                }
+            },
+        };
+
+        self.start_paste(pattern);
+
+        true
+    }
+
+    /// Lets a pattern be placed with a click, like
+    /// [`paste_from_clipboard()`](Self::paste_from_clipboard()).
+    pub fn start_paste(&mut self, pattern: Pattern) {
+        self.shell.pending_paste = Some(pattern);
+        self.update_exit_key();
+        self.toast("Click to place the pattern, Escape to cancel");
+    }
+
+    /// Cancels placing a pasted pattern.
+    pub fn cancel_paste(&mut self) {
+        self.shell.pending_paste = None;
+        self.update_exit_key();
+    }
+
+    /// Shows a crosshair while the mouse is over the grid and a pasted
+    /// pattern waits to be placed, and the default cursor otherwise
+    /// (including when it's outside of the window).
+    fn update_cursor(&mut self) {
+        let placing = self.shell.pending_paste.is_some()
+            && self.rl.is_cursor_on_screen()
+            && self.screen_to_cell(self.mouse_position()).is_some();
+        let cursor = if placing {
+            MouseCursor::MOUSE_CURSOR_CROSSHAIR
+        } else {
+            MouseCursor::MOUSE_CURSOR_DEFAULT
+        };
+
+        // NOTE: Only set when it changes, as it's checked every frame
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            self.rl.set_mouse_cursor(cursor);
+        }
+    }
+
+    /// Hides the system cursor while it's over the window, or shows it
+    /// again.
+    ///
+    /// A custom one may be drawn instead with an overlay (see
+    /// [`add_overlay()`](Self::add_overlay())) at
+    /// [`mouse_position()`](Self::mouse_position()).
+    pub fn set_cursor_hidden(&mut self, hidden: bool) {
+        if hidden == self.cursor_hidden {
+            return;
+        }
+
+        self.cursor_hidden = hidden;
+        if hidden {
+            self.rl.hide_cursor();
+        } else {
+            self.rl.show_cursor();
+        }
+    }
+
+    /// Checks whether the system cursor is hidden (see
+    /// [`set_cursor_hidden()`](Self::set_cursor_hidden())).
+    pub const fn is_cursor_hidden(&self) -> bool {
+        self.cursor_hidden
+    }
+
+    /// Lets Escape close the window only if it has nothing else to
+    /// cancel (a paste or the pattern library list).
+    fn update_exit_key(&mut self) {
+        let busy = self.shell.pending_paste.is_some()
+            || self.selection.is_some()
+            || self.shell.library_selected.is_some();
+
+        self.rl
+            .set_exit_key((!busy).then_some(KeyboardKey::KEY_ESCAPE));
+    }
+
+    /// Sets the pattern library browsed with P (see
+    /// [`default_key_actions()`](Self::default_key_actions())), or
+    /// removes it if [`None`] is given.
+    pub fn set_pattern_library(&mut self, library: Option<PatternLibrary>) {
+        self.shell.library = library;
+        self.shell.library_selected = None;
+        self.update_exit_key();
+    }
+
+    /// Returns the pattern library, if one was set.
+    pub const fn pattern_library(&self) -> Option<&PatternLibrary> {
+        self.shell.library.as_ref()
+    }
+
+    /// Starts placing the library pattern with the given name, like
+    /// [`paste_from_clipboard()`](Self::paste_from_clipboard()).
+    ///
+    /// Returns whether the pattern was found and loaded, a toast tells
+    /// why not.
+    pub fn place_library_pattern(&mut self, name: &str) -> bool {
+        let pattern = match self
+            .library
+            .as_ref()
+            .and_then(|library| library.get_by_name(name))
+        {
+            Some(Ok(pattern)) => Ok(pattern.clone()),
+            Some(Err(error)) => Err(format!("Invalid pattern: {error}")),
+            None => Err(format!("No pattern named {name}")),
+        };
+
+        match pattern {
+            Ok(pattern) => {
+                self.start_paste(pattern);
+                true
+            }
+            Err(error) => {
+                self.toast(error);
+                false
+            }
+        }
+    }
+
+    /// Shows or hides the list of the library patterns.
+    pub fn toggle_library_list(&mut self) {
+        if self.shell.library.is_none() {
+            self.toast("No pattern library (see `set_pattern_library()`)");
+            return;
+        }
+
+        self.shell.library_selected = self.shell.library_selected.xor(Some(0));
+        self.update_exit_key();
+    }
+
+    /// Handles a key press while the library list is shown: arrows move
+    /// the selection, Enter picks the pattern, Escape closes the list.
+    fn library_list_key(&mut self, key: KeyboardKey) {
+        let (Some(library), Some(selected)) = (&self.shell.library, self.shell.library_selected)
+        else {
+            return;
+        };
+
+        match key {
+            KeyboardKey::KEY_UP => self.shell.library_selected = Some(selected.saturating_sub(1)),
+            KeyboardKey::KEY_DOWN => {
+                self.shell.library_selected = Some(
+                    selected
+                        .saturating_add(1)
+                        .min(library.len().saturating_sub(1)),
+                );
+            }
+            KeyboardKey::KEY_ENTER => {
+                let name = library.names().nth(selected).map(str::to_owned);
+
+                self.toggle_library_list();
+                if let Some(name) = name {
+                    self.place_library_pattern(&name);
+                }
+            }
+            KeyboardKey::KEY_ESCAPE | KeyboardKey::KEY_P => self.toggle_library_list(),
+            _ => (),
+        }
+    }
+
+    /// Places the pasted pattern with its top-left corner at the cell
+    /// under the cursor, if there's one.
+    fn place_paste(&mut self) {
+        let Some(origin) = self.screen_to_cell(self.mouse_position()) else {
+            return;
+        };
+        let (Some(pattern), Some(paste_cell)) = (&self.shell.pending_paste, &self.paste_cell)
+        else {
+            return;
+        };
+        let cells: Vec<_> = self
+            .paste_positions(pattern, origin)
+            .map(|pos| (pos, paste_cell()))
+            .collect();
+        let placed = self.apply_pattern((0, 0), cells);
+
+        self.cancel_paste();
+        self.toast(format!("Placed {placed} cells"));
+    }
+
+    /// Returns the positions of the cells of a pattern placed at
+    /// `origin`, leaving out the ones outside of the grid.
+    fn paste_positions<'a>(
+        &self,
+        pattern: &'a Pattern,
+        (origin_x, origin_y): (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = self.grid_size;
+
+        pattern
+            .cells
+            .iter()
+            .filter_map(move |&(x, y)| Some((origin_x.checked_add(x)?, origin_y.checked_add(y)?)))
+            .filter(move |&(x, y)| x < width && y < height)
+    }
+
+    /// Replaces the contents of `rects` with the screen rectangles of the
+    /// cells of the pasted pattern as it would be placed at the cursor.
+    fn paste_ghost(&self, rects: &mut Vec<Rectangle>) {
+        rects.clear();
+
+        let (Some(pattern), Some(origin)) = (
+            &self.shell.pending_paste,
+            self.screen_to_cell(self.mouse_position()),
+        ) else {
+            return;
+        };
+        let context = self.overlay_context();
+        let (xs, ys) = self.visible_cells();
+
+        rects.extend(
+            self.paste_positions(pattern, origin)
+                .filter(|(x, y)| xs.contains(x) && ys.contains(y))
+                .map(|pos| context.cell_rect(pos)),
+        );
+    }
+
+    /// Checks whether either of the Ctrl keys is held down.
+    fn ctrl_down(&self) -> bool {
+        self.rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL)
+            || self.rl.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL)
+    }
+
+    /// Checks whether either of the Shift keys is held down.
+    fn shift_down(&self) -> bool {
+        self.rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT)
+            || self.rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT)
+    }
+
+    /// Selects the cells dragged over with Shift+LMB.
+    fn drag_selection(&mut self) {
+        let cell = self.screen_to_cell(self.mouse_position());
+
+        if self.shell.pending_paste.is_none()
+            && self.shift_down()
+            && self
+                .rl
+                .is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT)
+        {
+            self.selection_anchor = cell;
+        }
+        if !self.rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
+            self.selection_anchor = None;
+        }
+        if let (Some(anchor), Some(cell)) = (self.selection_anchor, cell) {
+            self.select(anchor, cell);
+        }
+    }
+
+    /// Returns the screen rectangle around the selected cells.
+    fn selection_rect(&self) -> Option<Rectangle> {
+        let (min, max) = self.selection?;
+        let context = self.overlay_context();
+        let (first, last) = (context.cell_rect(min), context.cell_rect(max));
+
+        Some(Rectangle::new(
+            first.x,
+            first.y,
+            last.x + last.width - first.x,
+            last.y + last.height - first.y,
+        ))
+    }
+
+    /// Registers default key actions:
+    /// - Space -> Pause
+    /// - L -> Toggle the color legend
+    /// - F3 -> Toggle the FPS counter
+    /// - F4 -> Toggle the step and draw times
+    /// - Tab -> Toggle turbo mode
+    /// - F11 -> Toggle fullscreen
+    /// - Comma / Period -> Step back / forward through the history (see
+    ///   [`set_history_length()`](Self::set_history_length()))
+    /// - Shift+LMB drag -> Select cells, Ctrl+A selects all of them and
+    ///   Escape deselects them (see [`select()`](Self::select()))
+    /// - Ctrl+C -> Copy the selected cells as RLE (see
+    ///   [`copy_selection_to_clipboard()`](Self::copy_selection_to_clipboard()))
+    /// - Ctrl+V -> Paste a pattern, placed with LMB or canceled with
+    ///   Escape (see [`paste_from_clipboard()`](Self::paste_from_clipboard()))
+    /// - P -> Show the pattern library, browsed with the arrows and
+    ///   Enter (see [`set_pattern_library()`](Self::set_pattern_library()))
+    /// - F5 / F9 -> Quick save / load, once enabled with
+    ///   [`set_quick_save_keys_enabled()`](Self::set_quick_save_keys_enabled())
+    /// - Ctrl+S / Ctrl+O -> Save / load the session with the `serde`
+    ///   feature, once enabled with `set_session_keys_enabled()`
+    /// - LMB -> Toggle cell under cursor
+    pub fn default_key_actions(&mut self) {
+        if self.shell.pending_paste.is_some()
+            && self
+                .rl
+                .is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT)
+        {
+            self.place_paste();
+        }
+        self.drag_selection();
+        if self.shell.library_selected.is_some() {
+            if let Some(key) = self.rl.get_key_pressed() {
+                self.library_list_key(key);
+            }
+            return;
+        }
+        if let Some(quick_save_actions) = self.shell.quick_save_hook {
+            quick_save_actions(self);
+        }
+        #[cfg(feature = "serde")]
+        if let Some(session_actions) = self.shell.session_hook {
+            session_actions(self);
+        }
+
+        match self.rl.get_key_pressed() {
+            None => (),
+            Some(key) => match key {
+                KeyboardKey::KEY_SPACE => self.toggle_pause(),
+                KeyboardKey::KEY_L => self.toggle_legend(),
+                KeyboardKey::KEY_F3 => self.toggle_fps(),
+                KeyboardKey::KEY_F4 => self.toggle_timings(),
+                KeyboardKey::KEY_TAB => self.toggle_turbo(),
+                KeyboardKey::KEY_F11 => self.toggle_fullscreen(),
+                KeyboardKey::KEY_COMMA => {
+                    self.step_back();
+                }
+                KeyboardKey::KEY_PERIOD => self.step_forward(),
+                KeyboardKey::KEY_V if self.ctrl_down() => {
+                    self.paste_from_clipboard();
+                }
+                KeyboardKey::KEY_ESCAPE => {
+                    self.cancel_paste();
+                    self.clear_selection();
+                }
+                KeyboardKey::KEY_P => self.toggle_library_list(),
+                KeyboardKey::KEY_C if self.ctrl_down() => {
+                    self.copy_selection_to_clipboard();
+                }
+                KeyboardKey::KEY_A if self.ctrl_down() => {
+                    let (width, height) = self.grid_size;
+
+                    self.select((0, 0), (width.saturating_sub(1), height.saturating_sub(1)));
+                }
+                // NOTE: Minus reduces the rate (not the time taken), equals
+                // increases the rate.
+                KeyboardKey::KEY_MINUS => {
+                    self.set_update_rate(
+                        self.timer.rate().saturating_add(Duration::from_millis(10)),
+                    );
+                    self.toast_update_rate();
+                }
+                KeyboardKey::KEY_EQUAL => {
+                    let duration = self
+                        .timer
+                        .rate()
+                        .checked_sub(Duration::from_millis(10))
+                        .unwrap_or(Duration::from_millis(0));
+
+                    self.set_update_rate(duration);
+                    self.toast_update_rate();
+                }
+                _ => (),
+            },
+        }
+    }
+}
+
+impl<S, D> RaylibFrontend<S, D> {
+    /// Runs a single frame of the usual loop: displays the grid,
+    /// handles the default key actions (see
+    /// [`default_key_actions()`](Self::default_key_actions())) and
+    /// computes the generations due (see [`tick()`](Self::tick())).
+    ///
+    /// Returns whether to keep going, i.e. `false` once the window
+    /// should close.  It's meant for loops driven from the outside,
+    /// e.g. by the browser (see the `web` feature), elsewhere it's the
+    /// same as:
+    ///
+    /// ```ignore
+    /// while !frontend.window_should_close() {
+    ///     frontend.display_grid();
+    ///     frontend.default_key_actions();
+    ///     frontend.tick();
+    /// }
+    /// ```
+    pub fn run_frame<C>(&mut self) -> bool
+    where
+        S: RenderCell<C> + MaybeSync,
+        C: Into<Color>,
+    {
+        if self.window_should_close() {
+            return false;
+        }
+
+        self.display_grid::<C>();
+        self.default_key_actions();
+        self.tick();
+
+        !self.window_should_close()
+    }
+
+    /// Displays the cell grid using Raylib.
+    ///
+    /// Manages the job of clearing the background and drawing all the
+    /// cells with respect to their [`RenderCell`] implementation.  Any
+    /// color type convertible into [`Color`] can be used.
+    pub fn display_grid<C>(&mut self)
+    where
+        S: RenderCell<C> + MaybeSync,
+        C: Into<Color>,
+    {
+        self.display_grid_with::<C, _>(|_, _| ());
+    }
+
+    /// Skips drawing while the window is minimized or hidden, sleeping a
+    /// bit if there's nothing to compute either, and redraws everything
+    /// once it's restored.
+    ///
+    /// Returns whether the frame was skipped.
+    fn skip_minimized_frame(&mut self) -> bool {
+        if self.is_minimized() {
+            self.shell.was_minimized = true;
+            // NOTE: An empty frame still polls the window events, so that
+            // restoring it is noticed
+            drop(self.rl.begin_drawing(&self.thread));
+            if self.is_paused() {
+                thread::sleep(MINIMIZED_FRAME_TIME);
+            }
+
+            return true;
+        }
+
+        if self.shell.was_minimized {
+            self.shell.was_minimized = false;
+            self.invalidate_layout();
+        }
+
+        false
+    }
+
+    /// Like [`display_grid()`](Self::display_grid()), but also calls
+    /// `f` after the grid and all the registered overlays are drawn.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub fn display_grid_with<C, F>(&mut self, f: F)
+    where
+        S: RenderCell<C> + MaybeSync,
+        C: Into<Color>,
+        F: FnOnce(&mut RaylibDrawHandle<'_>, &OverlayContext),
+    {
+        if self.skip_minimized_frame() {
+            return;
+        }
+
+        self.handle_window_events();
+        self.update_layout();
+        self.update_cursor();
+
+        let start = Instant::now();
+        let (xs, ys) = self.visible_cells();
+        let cells_drawn = xs.len().saturating_mul(ys.len());
+        self.background_cache.update(&self.background, &xs, &ys);
+        // NOTE: Small grids are drawn directly, as batching has some
+        // overhead of its own
+        let batched = xs.len().saturating_mul(ys.len()) >= BATCH_THRESHOLD;
+        let context = self.overlay_context();
+        let rect = Vector2::new(context.cell_size, context.cell_size);
+        // NOTE: Falls back to drawing the cells separately if the texture
+        // can't be created
+        let pixel_mode = (self.pixel_mode || self.detail_level() == DetailLevel::Pixels)
+            && self
+                .canvas
+                .prepare(&mut self.rl, &self.thread, (xs.len(), ys.len()))
+                .is_ok();
+        let batched = batched && !pixel_mode;
+        let first = (xs.start, ys.start);
+        let canvas_rect = {
+            let first = context.cell_rect(first);
+            let pitch = context.cell_size + context.cell_margin;
+
+            Rectangle::new(
+                first.x,
+                first.y,
+                xs.len() as f32 * pitch,
+                ys.len() as f32 * pitch,
+            )
+        };
+        let cache_key = (
+            self.generation,
+            self.snapshot.invalidations(),
+            [
+                self.zoom.to_bits(),
+                self.view_offset.x.to_bits(),
+                self.view_offset.y.to_bits(),
+            ],
+        );
+        // NOTE: The cache replaces the whole window, so it's only used
+        // if the window is cleared with an opaque color
+        let cached = self.grid_cache.enabled
+            && self.clear_color.a == u8::MAX
+            && self
+                .grid_cache
+                .prepare(
+                    &mut self.rl,
+                    &self.thread,
+                    self.layout.window_size,
+                    self.dpi_scale(),
+                )
+                .is_ok();
+        let redraw = !cached || !self.grid_cache.is_clean(&cache_key);
+        let mut bytes_uploaded = 0;
+        let refresh = redraw
+            && self.snapshot.begin(
+                self.generation,
+                (pixel_mode, [xs.start, xs.end, ys.start, ys.end]),
+            );
+        let stats = self.hud_context(&context);
+        // NOTE: Only what's drawn of the grid is counted, the HUD formats
+        // its text every frame
+        #[cfg(feature = "count-allocations")]
+        let allocations = alloc_count::allocations();
+        let mut paste_ghost = mem::take(&mut self.paste_ghost);
+
+        self.paste_ghost(&mut paste_ghost);
+
+        let mut drawer = self.rl.begin_drawing(&self.thread);
+
+        if redraw {
+            if cached {
+                self.grid_cache.begin(&mut drawer, cache_key);
+            }
+
+            if self.layout.needs_clear(cached, self.always_clear) {
+                drawer.clear_background(self.clear_color);
+            }
+
+            // NOTE: Scissor regions aren't scaled by raylib within textures
+            let scale = if cached { self.grid_cache.scale() } else { 1. };
+            let grid_rect = self.layout.grid_rect;
+            let (x, y, width, height) = layout::scissor_rect(Rectangle::new(
+                grid_rect.x * scale,
+                grid_rect.y * scale,
+                grid_rect.width * scale,
+                grid_rect.height * scale,
+            ));
+            let mut scissor = drawer.begin_scissor_mode(x, y, width, height);
+
+            let rows = ys.len();
+            let dense =
+                refresh && self.automaton.cells().len().saturating_mul(DENSE_RATIO) >= cells_drawn;
+            let dense_key = (self.generation, self.snapshot.invalidations());
+
+            if dense && !self.dense_colors.is_fresh(dense_key, self.grid_size) {
+                self.dense_colors.fill(
+                    dense_key,
+                    self.grid_size,
+                    self.automaton
+                        .cells()
+                        .iter()
+                        .map(|(&pos, cell)| (pos, cell.render_cell().into())),
+                );
+            }
+
+            // NOTE: The visible cells are rendered in parallel up front, and
+            // drawn in order afterwards
+            #[cfg(feature = "rayon")]
+            if refresh && !dense {
+                let cells = self.automaton.cells();
+                let ys = ys.clone();
+
+                self.rendered_colors.clear();
+                self.rendered_colors
+                    .par_extend(xs.clone().into_par_iter().flat_map_iter(|x| {
+                        ys.clone().map(move |y| {
+                            cells
+                                .get(&(x, y))
+                                .map(|cell| -> Color { cell.render_cell().into() })
+                        })
+                    }));
+            }
+            // NOTE: The positions of the visible columns and rows are
+            // computed once per frame rather than for every cell
+            self.column_xs.clear();
+            self.column_xs
+                .extend(xs.clone().map(|x| context.cell_rect((x, 0)).x));
+            self.row_ys.clear();
+            self.row_ys
+                .extend(ys.clone().map(|y| context.cell_rect((0, y)).y));
+            // NOTE: The texture keeps the last rendered grid on its own
+            if refresh || !pixel_mode {
+                xs.for_each(|x| {
+                    ys.clone().for_each(|y| {
+                        let pos = Vector2::new(
+                            self.column_xs
+                                .get(x.saturating_sub(first.0))
+                                .copied()
+                                .unwrap_or_default(),
+                            self.row_ys
+                                .get(y.saturating_sub(first.1))
+                                .copied()
+                                .unwrap_or_default(),
+                        );
+                        let index = x
+                            .saturating_sub(first.0)
+                            .saturating_mul(rows)
+                            .saturating_add(y.saturating_sub(first.1));
+                        let color = if refresh {
+                            // HACK: Unify types (`usize`)
+                            #[cfg(not(feature = "rayon"))]
+                            let rendered = || -> Option<Color> {
+                                self.automaton
+                                    .cells()
+                                    .get(&(x, y))
+                                    .map(|c| c.render_cell().into())
+                            };
+                            #[cfg(feature = "rayon")]
+                            let rendered = || self.rendered_colors.get(index).copied().flatten();
+                            let rendered = if dense {
+                                self.dense_colors.color_at((x, y))
+                            } else {
+                                rendered()
+                            };
+                            let color = rendered.unwrap_or_else(|| {
+                                self.background_cache
+                                    .color_at((x, y))
+                                    .unwrap_or_else(|| self.background.color_at((x, y)))
+                            });
+
+                            self.snapshot.record(color);
+                            color
+                        } else {
+                            self.snapshot.color(index)
+                        };
+
+                        if pixel_mode {
+                            self.canvas.set(
+                                (x.saturating_sub(first.0), y.saturating_sub(first.1)),
+                                color,
+                            );
+                        } else if batched {
+                            self.batch.push(pos, color);
+                        } else {
+                            scissor.draw_rectangle_v(pos, rect, color);
+                        }
+                    })
+                });
+            }
+
+            if batched {
+                self.batch.flush(&mut scissor, rect);
+            }
+            if pixel_mode {
+                bytes_uploaded = self.canvas.draw(&mut scissor, canvas_rect);
+            }
+            drop(scissor);
+
+            if cached {
+                self.grid_cache.end(&mut drawer);
+            }
+        }
+        if cached {
+            self.grid_cache.draw(&mut drawer);
+        }
+
+        paste_ghost
+            .iter()
+            .for_each(|&rect| drawer.draw_rectangle_rec(rect, PASTE_GHOST_COLOR));
+        self.paste_ghost = paste_ghost;
+        if let Some(rect) = self.selection_rect() {
+            drawer.draw_rectangle_lines_ex(rect, SELECTION_LINE_WIDTH, SELECTION_COLOR);
+        }
+        #[cfg(feature = "count-allocations")]
+        self.frame_timer
+            .record_allocations(alloc_count::allocations().wrapping_sub(allocations));
+
+        let mut hud = HudStack::new(
+            stats.window_size,
+            self.shell.hud.style,
+            TextRenderer::new(self.font.as_ref()),
+        );
+        if let Some(status_bar) = &self.status_bar {
+            status_bar.draw(&mut drawer, hud.renderer, &stats);
+            hud.reserve(status_bar);
+        }
+        self.shell.hud.draw(&mut drawer, &mut hud, &stats);
+        self.legend.draw(&mut drawer, &mut hud);
+        if let Some(graph) = &self.graph {
+            graph.draw(&mut drawer, &mut hud);
+        }
+        if let (Some(library), Some(selected)) = (&self.shell.library, self.shell.library_selected)
+        {
+            library.draw_list(&mut drawer, &mut hud, selected);
+        }
+        self.shell.toasts.draw(&mut drawer, &mut hud);
+
+        self.overlays
+            .iter_mut()
+            .for_each(|overlay| overlay(&mut drawer, &context));
+        if let Some(hud_callback) = &mut self.shell.hud_callback {
+            hud_callback(&mut drawer, &stats);
+        }
+        f(&mut drawer, &context);
+
+        self.frame_timer.record_draw(
+            start.elapsed(),
+            if redraw { cells_drawn } else { 0 },
+            bytes_uploaded,
+        );
+        drop(drawer);
+        self.export_frame::<C>();
+        #[cfg(feature = "video")]
+        self.export_video_frame::<C>();
+        self.pace_frame();
+    }
+
+    /// Renders the grid into an image of the given size, which doesn't
+    /// have to match the window, along with the HUD if `include_hud` is
+    /// set.
+    ///
+    /// The window is scaled to fit the image, keeping its aspect ratio.
+    /// Overlays (see [`add_overlay()`](Self::add_overlay())) and the
+    /// [`set_hud()`](Self::set_hud()) callback draw on the window
+    /// directly, so they are left out.
+    ///
+    /// Fails if the texture to render into can't be created.
+    pub fn render_to_image<C>(
+        &mut self,
+        size: (u32, u32),
+        include_hud: bool,
+    ) -> Result<Image, String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let window_size = self.window_size();
+
+        self.handle_window_events();
+        self.update_layout();
+        self.render_area::<C>(size, layout::window_rect(window_size), include_hud)
+    }
+
+    /// Renders exactly the part of the grid shown in the window, without
+    /// the HUD, into an image of the given size, like
+    /// [`render_to_image()`](Self::render_to_image()).
+    ///
+    /// If the aspect ratios differ, the view is centered in the image
+    /// with empty bars along the sides.
+    pub fn render_view<C>(&mut self, resolution: (u32, u32)) -> Result<Image, String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        self.handle_window_events();
+        self.update_layout();
+        self.render_area::<C>(resolution, self.layout.grid_rect, false)
+    }
+
+    /// Renders the view like [`render_view()`](Self::render_view()) and
+    /// saves it to a PNG file (the path should end with `.png`).
+    ///
+    /// Fails if the image can't be rendered or the file can't be
+    /// written.
+    pub fn export_view<C>(
+        &mut self,
+        path: impl AsRef<Path>,
+        resolution: (u32, u32),
+    ) -> Result<(), String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let image = self.render_view::<C>(resolution)?;
+
+        recorder::write_image(&image, path.as_ref())
+    }
+
+    /// Renders an area of the window scaled to fit an image of the
+    /// given size, along with the HUD if `include_hud` is set.
+    fn render_area<C>(
+        &mut self,
+        size: (u32, u32),
+        area: Rectangle,
+        include_hud: bool,
+    ) -> Result<Image, String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        if size.0 == 0 || size.1 == 0 {
+            return Err("image size must not be zero".to_owned());
+        }
+
+        let context = self.overlay_context();
+        let stats = self.hud_context(&context);
+        let camera = layout::fit_camera(size, area);
+        let rect = Vector2::new(context.cell_size, context.cell_size);
+        let (xs, ys) = self.visible_cells();
+        let batched = xs.len().saturating_mul(ys.len()) >= BATCH_THRESHOLD;
+        let mut target = self.rl.load_render_texture(&self.thread, size.0, size.1)?;
+        let mut texture = self.rl.begin_texture_mode(&self.thread, &mut target);
+
+        texture.clear_background(self.clear_color);
+
+        let (x, y, width, height) = layout::scissor_rect(Rectangle::new(
+            (self.layout.grid_rect.x - camera.target.x).mul_add(camera.zoom, camera.offset.x),
+            (self.layout.grid_rect.y - camera.target.y).mul_add(camera.zoom, camera.offset.y),
+            self.layout.grid_rect.width * camera.zoom,
+            self.layout.grid_rect.height * camera.zoom,
+        ));
+        let mut scissor = texture.begin_scissor_mode(x, y, width, height);
+        let mut drawer = scissor.begin_mode2D(camera);
+
+        xs.for_each(|x| {
+            ys.clone().for_each(|y| {
+                let Rectangle {
+                    x: pos_x, y: pos_y, ..
+                } = context.cell_rect((x, y));
+                let pos = Vector2::new(pos_x, pos_y);
+                let color = self.automaton.cells().get(&(x, y)).map_or_else(
+                    || self.background.color_at((x, y)),
+                    |c| c.render_cell().into(),
+                );
+
+                if batched {
+                    self.batch.push(pos, color);
+                } else {
+                    drawer.draw_rectangle_v(pos, rect, color);
+                }
+            });
+        });
+        if batched {
+            self.batch.flush(&mut drawer, rect);
+        }
+        drop(drawer);
+        drop(scissor);
+
+        if include_hud {
+            let mut drawer = texture.begin_mode2D(camera);
+            let mut hud = HudStack::new(
+                stats.window_size,
+                self.shell.hud.style,
+                TextRenderer::new(self.font.as_ref()),
+            );
+
+            if let Some(status_bar) = &self.status_bar {
+                status_bar.draw(&mut drawer, hud.renderer, &stats);
+                hud.reserve(status_bar);
+            }
+            self.shell.hud.draw(&mut drawer, &mut hud, &stats);
+            self.legend.draw(&mut drawer, &mut hud);
+            if let Some(graph) = &self.graph {
+                graph.draw(&mut drawer, &mut hud);
+            }
+            self.shell.toasts.draw(&mut drawer, &mut hud);
+        }
+        drop(texture);
+
+        let mut image = target.texture().load_image()?;
+
+        // NOTE: Render textures are stored upside down
+        image.flip_vertical();
+
+        Ok(image)
+    }
+
+    /// Starts writing the grid to numbered PNG files
+    /// (`frame_000001.png`, ...) in the given directory every `every`
+    /// generations, e.g. to make a video of the run.
+    ///
+    /// The frames are rendered at the size of the window (see
+    /// [`render_to_image()`](Self::render_to_image())) when the grid is
+    /// displayed, and written on a background thread.  If one can't be
+    /// written, the export stops with a toast.
+    ///
+    /// Fails if the directory can't be created.
+    pub fn start_frame_export(&mut self, dir: impl AsRef<Path>, every: u64) -> io::Result<()> {
+        self.stop_frame_export();
+        self.frame_export = Some(FrameExport::start(dir.as_ref(), every)?);
+
+        Ok(())
+    }
+
+    /// Stops writing frames, waiting for the queued ones to be written.
+    pub fn stop_frame_export(&mut self) {
+        let Some(export) = self.frame_export.take() else {
+            return;
+        };
+
+        match export.finish() {
+            Ok(count) => self.toast(format!("Exported {count} frames")),
+            Err(error) => self.toast(format!("Frame export failed: {error}")),
+        }
+    }
+
+    /// Renders and queues a frame if one is due (see
+    /// [`start_frame_export()`](Self::start_frame_export())).
+    fn export_frame<C>(&mut self)
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let generation = self.generation;
+        let result = match &self.frame_export {
+            None => return,
+            Some(export) if !export.is_due(generation) => export.error(),
+            Some(_) => {
+                let size = self.framebuffer_size();
+
+                self.render_to_image::<C>(size, false).and_then(|image| {
+                    self.frame_export
+                        .as_mut()
+                        .map_or(Ok(()), |export| export.push(generation, image))
+                })
+            }
+        };
+
+        if let Err(error) = result {
+            self.frame_export = None;
+            self.toast(format!("Frame export failed: {error}"));
+        }
+    }
+
+    /// Records `generations` generations, starting with the current
+    /// one, into an animated GIF, with each cell taking up `scale` by
+    /// `scale` pixels and each frame shown for the update rate.
+    ///
+    /// The automaton is stepped (see [`step()`](Self::step())) while
+    /// recording, and the window isn't updated until it's done.  Frames
+    /// are written to the file as they're rendered.
+    ///
+    /// Fails if the file can't be written, or if the frames would be
+    /// larger than the format allows.
+    #[cfg(feature = "gif")]
+    pub fn record_gif<C>(
+        &mut self,
+        path: impl AsRef<Path>,
+        generations: usize,
+        scale: usize,
+    ) -> Result<(), String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let mut recorder =
+            GifRecorder::create(path.as_ref(), self.grid_size, scale, self.timer.rate())?;
+
+        for index in 0..generations {
+            if index > 0 {
+                if self.automaton.is_finished() {
+                    break;
+                }
+                self.step();
+            }
+            self.write_gif_frame(&mut recorder)?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders the cells as the next frame of a GIF.
+    #[cfg(feature = "gif")]
+    fn write_gif_frame<C>(&self, recorder: &mut GifRecorder) -> Result<(), String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let cells = self.automaton.cells();
+        let background = &self.background;
+
+        recorder.write_frame(|pos| {
+            cells.get(&pos).map_or_else(
+                || background.color_at(pos),
+                |cell| cell.render_cell().into(),
+            )
+        })
+    }
+
+    /// Registers the key to record the next 100 generations into a GIF
+    /// (G by default, see [`FrontendBuilder::gif_key()`]), named after
+    /// the current generation.
+    ///
+    /// Can be used along with
+    /// [`default_key_actions()`](Self::default_key_actions()).
+    #[cfg(feature = "gif")]
+    pub fn gif_actions<C>(&mut self)
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        if !self.rl.is_key_pressed(self.gif_key) {
+            return;
+        }
+
+        let path = format!("generation_{}.gif", self.generation);
+
+        match self.record_gif::<C>(&path, GIF_ACTION_GENERATIONS, GIF_ACTION_SCALE) {
+            Ok(()) => self.toast(format!("Saved {path}")),
+            Err(error) => self.toast(format!("GIF recording failed: {error}")),
+        }
+    }
+
+    /// Starts encoding the grid into a video by running an external
+    /// command (`ffmpeg` by default, see [`VideoConfig`]) and writing
+    /// raw frames to its input.
+    ///
+    /// The frames are rendered like with
+    /// [`render_to_image()`](Self::render_to_image()) when the grid is
+    /// displayed, and written on a background thread.  If the command
+    /// stops accepting them, the export stops with a toast.
+    ///
+    /// Fails if the command can't be started.
+    #[cfg(feature = "video")]
+    pub fn start_video_export(&mut self, config: &VideoConfig) -> io::Result<()> {
+        self.stop_video_export();
+        self.video_export = Some(VideoExport::start(config)?);
+
+        Ok(())
+    }
+
+    /// Stops the video export, waiting for the encoder to finish.
+    ///
+    /// This also happens when the frontend is dropped, but without
+    /// reporting errors.
+    #[cfg(feature = "video")]
+    pub fn stop_video_export(&mut self) {
+        let Some(export) = self.video_export.take() else {
+            return;
+        };
+
+        match export.finish() {
+            Ok(()) => self.toast("Video saved"),
+            Err(error) => self.toast(format!("Video export failed: {error}")),
+        }
+    }
+
+    /// Renders and queues a video frame if one is due (see
+    /// [`start_video_export()`](Self::start_video_export())).
+    #[cfg(feature = "video")]
+    fn export_video_frame<C>(&mut self)
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let generation = self.generation;
+        let result = match &self.video_export {
+            None => return,
+            Some(export) if !export.is_due(generation) => export.error(),
+            Some(export) => {
+                let size = export.size();
+
+                self.render_to_image::<C>(size, false).and_then(|image| {
+                    let pixels = image
+                        .get_image_data()
+                        .iter()
+                        .flat_map(|color| [color.r, color.g, color.b, color.a])
+                        .collect();
+
+                    self.video_export
+                        .as_mut()
+                        .map_or(Ok(()), |export| export.push(generation, pixels))
+                })
+            }
+        };
+
+        if let Err(error) = result {
+            self.video_export = None;
+            self.toast(format!("Video export failed: {error}"));
+        }
+    }
+
+    /// Renders the grid like [`render_to_image()`](Self::render_to_image())
+    /// and saves it to a PNG file (the path should end with `.png`).
+    ///
+    /// Fails if the image can't be rendered or the file can't be
+    /// written.
+    pub fn save_png<C>(
+        &mut self,
+        path: impl AsRef<Path>,
+        size: (u32, u32),
+        include_hud: bool,
+    ) -> Result<(), String>
+    where
+        S: RenderCell<C>,
+        C: Into<Color>,
+    {
+        let image = self.render_to_image::<C>(size, include_hud)?;
+
+        recorder::write_image(&image, path.as_ref())
+    }
+}
+
+/// A saved state of the automaton (see [`RaylibFrontend::snapshot()`]).
+pub struct StateSnapshot<S, D> {
+    automaton: Automaton<S, D>,
+    generation: u64,
+}
+
+impl<S, D> StateSnapshot<S, D> {
+    /// Returns the generation the state was saved at.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<S, D> RaylibFrontend<S, D>
+where
+    Automaton<S, D>: Clone,
+{
+    /// Saves the current state of the automaton along with the
+    /// generation number.
+    pub fn snapshot(&self) -> StateSnapshot<S, D> {
+        StateSnapshot {
+            automaton: self.automaton.clone(),
+            generation: self.generation,
+        }
+    }
+
+    /// Returns to a saved state (see [`snapshot()`](Self::snapshot())).
+    ///
+    /// The graph and the detection of stable states start over.
+    pub fn restore(&mut self, snapshot: &StateSnapshot<S, D>) {
+        self.cancel_steps();
+        self.automaton = snapshot.automaton.clone();
+        self.restart_from(snapshot.generation);
+        self.step_stats = None;
+        self.diff = None;
+        self.update_title();
+    }
+
+    /// Keeps the last `length` generations to step back through them
+    /// (see [`step_back()`](Self::step_back())), or stops keeping them
+    /// if `0` is given.
+    ///
+    /// Each generation is a full copy of the automaton, so this takes up
+    /// `length` times as much memory as the automaton itself.
+    pub fn set_history_length(&mut self, length: usize) {
+        self.shell.history = (length > 0).then(|| History::new(length, Automaton::clone));
+        if let Some(history) = &mut self.shell.history {
+            history.record(&self.automaton, self.generation);
+        }
+    }
+
+    /// Makes [`default_key_actions()`](Self::default_key_actions())
+    /// handle the quick save keys as well (see
+    /// [`quick_save_actions()`](Self::quick_save_actions())), or stop
+    /// handling them.
+    pub fn set_quick_save_keys_enabled(&mut self, enabled: bool) {
+        let hook: fn(&mut Self) = Self::quick_save_actions;
+
+        self.shell.quick_save_hook = enabled.then_some(hook);
+    }
+
+    /// Registers the keys to save the state and return to it (F5 and F9
+    /// by default, see [`FrontendBuilder::quick_save_keys()`]).
+    ///
+    /// Can be used along with
+    /// [`default_key_actions()`](Self::default_key_actions()), or left
+    /// to it with
+    /// [`set_quick_save_keys_enabled()`](Self::set_quick_save_keys_enabled()).
+    pub fn quick_save_actions(&mut self) {
+        let (save, load) = self.quick_save_keys;
+
+        if self.rl.is_key_pressed(save) {
+            self.quick_save = Some(self.snapshot());
+            self.toast(format!("Saved generation {}", self.generation));
+        } else if self.rl.is_key_pressed(load) {
+            let Some(snapshot) = self.quick_save.take() else {
+                self.toast("Nothing saved yet");
+                return;
+            };
+
+            self.restore(&snapshot);
+            self.quick_save = Some(snapshot);
+            self.toast(format!("Loaded generation {}", self.generation));
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, D> RaylibFrontend<S, D>
+where
+    S: Serialize + DeserializeOwned,
+{
+    /// Saves the cells, the generation, the update rate, whether the
+    /// simulation is paused, the grid size and the view (see
+    /// [`set_zoom()`](Self::set_zoom())) to a JSON file, to resume
+    /// later with [`load_state()`](Self::load_state()).
+    pub fn save_state(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let mut cells: Vec<_> = self.automaton.cells().iter().collect();
+
+        // NOTE: Sorted so that the same state is always saved the same way
+        cells.sort_unstable_by_key(|&(&(x, y), _)| (y, x));
+
+        session::save(
+            path.as_ref(),
+            &SessionState {
+                version: session::FORMAT_VERSION,
+                generation: self.generation,
+                update_rate: self.timer.rate(),
+                paused: self.is_paused(),
+                grid_size: self.grid_size,
+                view: Some(View {
+                    zoom: self.zoom,
+                    offset: (self.view_offset.x, self.view_offset.y),
+                }),
+                cells,
+            },
+        )
+    }
+
+    /// Loads a state saved with [`save_state()`](Self::save_state()).
+    ///
+    /// Fails if the file can't be read, isn't a saved state, was saved
+    /// by a newer version, or if its grid size is invalid.
+    pub fn load_state(&mut self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let state: SessionState<Vec<((usize, usize), S)>> = session::load(path.as_ref())?;
+
+        self.set_grid_size(state.grid_size)
+            .map_err(SessionError::Mismatch)?;
+
+        self.cancel_steps();
+        let cells = self.automaton.cells_mut();
+
+        cells.clear();
+        cells.extend(state.cells);
+        if let Some(view) = state.view {
+            self.set_zoom(view.zoom);
+            self.set_view_offset(view.offset);
+        }
+        self.restart_from(state.generation);
+        self.step_stats = None;
+        self.diff = None;
+        self.set_update_rate(state.update_rate);
+        if state.paused {
+            self.pause();
+        } else {
+            self.resume();
+        }
+        self.update_title();
+
+        Ok(())
+    }
+
+    /// Saves the state (see [`save_state()`](Self::save_state())) into
+    /// `dir` every `every_n` generations, removing the older files so
+    /// that only the last `keep_last` remain.
+    ///
+    /// If a state can't be saved, autosaving stops with a toast.  Fails
+    /// if the directory can't be created.
+    pub fn autosave(&mut self, every_n: u64, dir: PathBuf, keep_last: usize) -> io::Result<()> {
+        self.shell.autosave = Some(Autosave::start(
+            dir,
+            every_n,
+            keep_last,
+            self.generation,
+            |frontend: &Self, path: &Path| {
+                frontend.save_state(path).map_err(|error| error.to_string())
+            },
+        )?);
+
+        Ok(())
+    }
+
+    /// Stops saving the state periodically.
+    pub fn stop_autosave(&mut self) {
+        self.shell.autosave = None;
+    }
+
+    /// Makes [`default_key_actions()`](Self::default_key_actions())
+    /// handle Ctrl+S and Ctrl+O as well (see
+    /// [`session_actions()`](Self::session_actions())), or stop
+    /// handling them.
+    pub fn set_session_keys_enabled(&mut self, enabled: bool) {
+        let hook: fn(&mut Self) = Self::session_actions;
+
+        self.shell.session_hook = enabled.then_some(hook);
+    }
+
+    /// Registers Ctrl+S and Ctrl+O to save the state and load it back
+    /// (see [`save_state()`](Self::save_state()) and
+    /// [`FrontendBuilder::session_path()`]).
+    ///
+    /// Can be used along with
+    /// [`default_key_actions()`](Self::default_key_actions()), or left
+    /// to it with
+    /// [`set_session_keys_enabled()`](Self::set_session_keys_enabled()).
+    pub fn session_actions(&mut self) {
+        if !self.ctrl_down() {
+            return;
+        }
+
+        let path = self.session_path.clone();
+
+        if self.rl.is_key_pressed(KeyboardKey::KEY_S) {
+            match self.save_state(&path) {
+                Ok(()) => self.toast(format!("Saved to {}", path.display())),
+                Err(error) => self.toast(format!("Saving failed: {error}")),
+            }
+        } else if self.rl.is_key_pressed(KeyboardKey::KEY_O) {
+            match self.load_state(&path) {
+                Ok(()) => self.toast(format!("Loaded generation {}", self.generation)),
+                Err(error) => self.toast(format!("Loading failed: {error}")),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, D> RaylibFrontend<S, D>
+where
+    S: Serialize,
+    D: Serialize,
+{
+    /// Writes the states of the live cells, the data computed for them
+    /// by the automaton, or both, to a JSON file as lists of
+    /// `{"x": .., "y": .., "value": ..}` records (see
+    /// [`json`](crate::json)).
+    ///
+    /// The cells are written while they're serialized, so the whole
+    /// document is never kept in memory.  Fails if the file can't be
+    /// written or a cell can't be serialized.
+    pub fn export_json(
+        &self,
+        path: impl AsRef<Path>,
+        what: ExportSelection,
+    ) -> serde_json::Result<()> {
+        json::write(
+            path.as_ref(),
+            self.grid_size,
+            what,
+            json::Records(self.automaton.cells()),
+            json::Records(self.automaton.infos()),
+        )
+    }
+}
+
+/// A helper struct to instantiate a [`RaylibFrontend`].
+pub struct FrontendBuilder {
+    window_size: (u32, u32),
+    cell_margin: u32,
+    update_rate: Duration,
+    init_grid_size: (usize, usize),
+    default_color: Color,
+    background: Option<Background>,
+    partial_cells: bool,
+    always_clear: bool,
+    clear_color: Color,
+    window_flags: WindowFlags,
+    legend: Vec<(String, Color)>,
+    legend_corner: Corner,
+    pixel_mode: bool,
+    detail_thresholds: (f32, f32),
+    frame_pacing: Option<Duration>,
+    cache_grid: bool,
+    texture_filter: TextureFilter,
+    partial_upload_threshold: f32,
+    show_generation: bool,
+    show_fps: bool,
+    show_population: bool,
+    show_rate: bool,
+    show_paused: bool,
+    paused_text: Cow<'static, str>,
+    hud_style: HudStyle,
+    hud_corner: Corner,
+    margin_mode: MarginMode,
+    status_bar: Option<StatusBar>,
+    show_graph: bool,
+    graph_length: usize,
+    graph_corner: Corner,
+    graph_series: GraphSeries,
+    track_step_stats: bool,
+    track_diff: bool,
+    max_toasts: usize,
+    toast_style: HudStyle,
+    toast_corner: Corner,
+    font: Option<(String, i32)>,
+    max_generations: Option<u64>,
+    show_progress: bool,
+    window_title: Cow<'static, str>,
+    title_format: Option<Cow<'static, str>>,
+    start_paused: bool,
+    rate_unit: RateUnit,
+    max_steps_per_frame: u32,
+    close_on_finish: bool,
+    pause_period: usize,
+    period_pauses: bool,
+    turbo_budget: Duration,
+    turbo: bool,
+    dpi_scale: Option<f32>,
+    max_frame_time: Duration,
+    render_every: usize,
+    pause_on_focus_loss: bool,
+    simulate_when_minimized: bool,
+    headless: bool,
+    hide_cursor: bool,
+    quick_save_keys: (KeyboardKey, KeyboardKey),
+    #[cfg(feature = "gif")]
+    gif_key: KeyboardKey,
+    #[cfg(feature = "serde")]
+    session_path: PathBuf,
+    initial_pattern: Option<PatternSource>,
+}
+
+impl<S, D> RaylibFrontend<S, D>
+where
+    Automaton<S, D>: Clone + Send + 'static,
+{
+    /// Moves computing the generations to a background thread, or back
+    /// to the main one, so that slow rules don't hold up drawing and
+    /// handling the input.
+    ///
+    /// The window keeps showing the last generation computed while the
+    /// next ones are, each of them being sent back whole.  Editing the
+    /// cells drops the generations being computed, which then start
+    /// over from the edited state.  Stepping explicitly (e.g. with
+    /// [`step_until()`](Self::step_until())) is still done on the main
+    /// thread.
+    ///
+    /// Fails if the thread can't be spawned.  It's stopped when the
+    /// frontend is dropped, once it's done with the current generation.
+    pub fn set_threaded(&mut self, threaded: bool) -> io::Result<()> {
+        if threaded == self.is_threaded() {
+            return Ok(());
+        }
+
+        self.shell.stepper = if threaded {
+            Some(Stepper::start(|automaton: &mut Automaton<S, D>| {
+                automaton.step()
+            })?)
+        } else {
+            None
+        };
+
+        Ok(())
+    }
+}
+
+impl FrontendBuilder {
+    /// Sets the window size.
+    #[must_use]
+    pub const fn window_size(mut self, window_size: (u32, u32)) -> Self {
+        // NOTE: Assigned instead of using `..self` to stay `const`, as the
+        // rest of the builder can't be dropped at compile time
+        self.window_size = window_size;
+        self
+    }
+
+    /// Sets the cell margin (purely visual).
+    ///
+    /// It's dropped if the cells get too small (see
+    /// [`detail_thresholds()`](Self::detail_thresholds())).
+    #[must_use]
+    pub const fn cell_margin(mut self, cell_margin: u32) -> Self {
+        self.cell_margin = cell_margin;
+        self
+    }
+
+    /// Sets the update rate.
+    ///
+    /// This is the amount of time that passes between each generation
+    /// is computed and displayed.
+    #[must_use]
+    pub const fn update_rate(mut self, update_rate: Duration) -> Self {
+        self.update_rate = update_rate;
+        self
+    }
+
+    /// Sets the update rate as the number of generations per second.
+    ///
+    /// `0` makes the simulation start paused (see
+    /// [`RaylibFrontend::set_steps_per_second()`]).
+    #[must_use]
+    pub fn steps_per_second(self, steps_per_second: f32) -> Self {
+        match period_from_frequency(steps_per_second) {
+            Some(update_rate) => Self {
+                update_rate,
+                start_paused: false,
+                ..self
+            },
+            None => Self {
+                start_paused: true,
+                ..self
+            },
+        }
+    }
+
+    /// Sets the maximum number of generations computed by a single
+    /// [`RaylibFrontend::tick()`] (1000 by default).
+    ///
+    /// If the update rate is faster than the frame rate, several
+    /// generations are computed per frame to keep up.  Lower this if
+    /// steps are slow enough to drag down the frame rate.
+    #[must_use]
+    pub fn max_steps_per_frame(self, max_steps_per_frame: u32) -> Self {
+        Self {
+            max_steps_per_frame,
+            ..self
+        }
+    }
+
+    /// Sets the longest time between frames that is caught up with
+    /// (250 ms by default).
+    ///
+    /// Generations are computed at a fixed rate regardless of the frame
+    /// rate, but after a longer hitch (e.g. while the window is being
+    /// dragged) the rest of the time is dropped instead of computing
+    /// all the missed generations at once.
+    #[must_use]
+    pub fn max_frame_time(self, max_frame_time: Duration) -> Self {
+        Self {
+            max_frame_time,
+            ..self
+        }
+    }
+
+    /// Sets how often the grid is rendered, in generations (every
+    /// generation by default, see [`RaylibFrontend::set_render_every()`]).
+    #[must_use]
+    pub fn render_every(self, render_every: usize) -> Self {
+        Self {
+            render_every,
+            ..self
+        }
+    }
+
+    /// Sets whether to pause the simulation while the window isn't
+    /// focused (off by default).
+    ///
+    /// If it was paused before losing focus, it stays paused after
+    /// regaining it.
+    #[must_use]
+    pub fn pause_on_focus_loss(self, pause_on_focus_loss: bool) -> Self {
+        Self {
+            pause_on_focus_loss,
+            ..self
+        }
+    }
+
+    /// Sets whether to keep computing generations while the window is
+    /// minimized or hidden (on by default).  Nothing is drawn then
+    /// either way.
+    ///
+    /// If it was paused before being minimized, it stays paused after
+    /// being restored.
+    #[must_use]
+    pub fn simulate_when_minimized(self, simulate_when_minimized: bool) -> Self {
+        Self {
+            simulate_when_minimized,
+            ..self
+        }
+    }
+
+    /// Sets whether to hide the window, e.g. to render images with
+    /// [`RaylibFrontend::render_to_image()`] from scripts (off by
+    /// default).
+    ///
+    /// Everything is drawn as usual, but the window is never shown (so
+    /// it gets no input) and it's only closed with
+    /// [`RaylibFrontend::request_close()`].  A display (or a virtual
+    /// one, e.g. Xvfb) is still needed to create it.
+    #[must_use]
+    pub fn headless(self, headless: bool) -> Self {
+        Self { headless, ..self }
+    }
+
+    /// Sets whether to hide the system cursor over the window (see
+    /// [`RaylibFrontend::set_cursor_hidden()`]).
+    #[must_use]
+    pub fn hide_cursor(self, hide_cursor: bool) -> Self {
+        Self {
+            hide_cursor,
+            ..self
+        }
+    }
+
+    /// Sets the keys to save the state and return to it (F5 and F9 by
+    /// default, see [`RaylibFrontend::quick_save_actions()`]).
+    #[must_use]
+    pub fn quick_save_keys(self, save: KeyboardKey, load: KeyboardKey) -> Self {
+        Self {
+            quick_save_keys: (save, load),
+            ..self
+        }
+    }
+
+    /// Sets the key to record a GIF (G by default, see
+    /// [`RaylibFrontend::gif_actions()`]).
+    #[cfg(feature = "gif")]
+    #[must_use]
+    pub fn gif_key(self, gif_key: KeyboardKey) -> Self {
+        Self { gif_key, ..self }
+    }
+
+    /// Sets the file the state is saved to and loaded from
+    /// (`session.json` by default, see
+    /// [`RaylibFrontend::session_actions()`]).
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn session_path(self, session_path: impl Into<PathBuf>) -> Self {
+        Self {
+            session_path: session_path.into(),
+            ..self
+        }
+    }
+
+    /// Sets the pattern placed in the middle of the grid by
+    /// [`finish_with_pattern()`](Self::finish_with_pattern()), e.g.
+    /// [`PatternSource::from_args()`].
+    #[must_use]
+    pub fn initial_pattern(self, initial_pattern: PatternSource) -> Self {
+        Self {
+            initial_pattern: Some(initial_pattern),
+            ..self
+        }
+    }
+
+    /// Sets whether to start in turbo mode (see
+    /// [`RaylibFrontend::set_turbo()`]).
+    #[must_use]
+    pub fn turbo(self, turbo: bool) -> Self {
+        Self { turbo, ..self }
+    }
+
+    /// Overrides the ratio of the framebuffer size to the window size
+    /// instead of querying it (see [`RaylibFrontend::dpi_scale()`]),
+    /// e.g. to test HiDPI rendering on a regular display.
+    #[must_use]
+    pub fn dpi_scale(self, dpi_scale: f32) -> Self {
+        Self {
+            dpi_scale: Some(dpi_scale),
+            ..self
+        }
+    }
+
+    /// Sets the time spent computing generations each frame in turbo
+    /// mode (10 ms by default, see [`RaylibFrontend::set_turbo()`]).
+    #[must_use]
+    pub fn turbo_budget(self, turbo_budget: Duration) -> Self {
+        Self {
+            turbo_budget,
+            ..self
+        }
+    }
+
+    /// Sets the unit the update rate is shown in (see
+    /// [`show_rate()`](Self::show_rate())).
+    #[must_use]
+    pub fn rate_unit(self, rate_unit: RateUnit) -> Self {
+        Self { rate_unit, ..self }
+    }
+
+    /// Sets the initial grid size.
+    ///
+    /// Since the life-like automata don't care about the grid size,
+    /// this value can later be changed (see
+    /// [`RaylibFrontend::set_grid_size()`]).
+    #[must_use]
+    pub const fn grid_size(mut self, init_grid_size: (usize, usize)) -> Self {
+        self.init_grid_size = init_grid_size;
+        self
+    }
+
+    /// Sets the color for "dead" cells.
+    ///
+    /// It's not used if a [`background()`](Self::background()) is set.
+    #[must_use]
+    pub const fn default_color(mut self, default_color: Color) -> Self {
+        self.default_color = default_color;
+        self
+    }
+
+    /// Sets the color for "dead" cells depending on their position.
+    ///
+    /// The closure should only depend on the position, as its results
+    /// are cached until the view changes.
+    #[must_use]
+    pub fn default_color_with<F: Fn((usize, usize)) -> Color + 'static>(self, f: F) -> Self {
+        self.background(Background::custom(f))
+    }
+
+    /// Draws "dead" cells as a checkerboard of the two given colors.
+    #[must_use]
+    pub fn checkerboard(self, even: Color, odd: Color) -> Self {
+        self.background(Background::Checkerboard(even, odd))
+    }
+
+    /// Sets how "dead" cells are drawn (see [`Background`]), instead of
+    /// the [`default_color()`](Self::default_color()).
+    #[must_use]
+    pub fn background(self, background: impl Into<Background>) -> Self {
+        Self {
+            background: Some(background.into()),
+            ..self
+        }
+    }
+
+    /// Makes the grid fill the whole window, drawing the cells that
+    /// don't fit entirely as clipped.
+    ///
+    /// This also lets [`RaylibFrontend::set_view_offset()`] scroll by
+    /// fractions of a cell without leaving empty borders.
+    #[must_use]
+    pub fn partial_cells(self, partial_cells: bool) -> Self {
+        Self {
+            partial_cells,
+            ..self
+        }
+    }
+
+    /// Forces clearing the background every frame.
+    ///
+    /// By default it's skipped when the cells cover the whole window,
+    /// which may leave artifacts if cells are rendered (partially)
+    /// transparent.
+    #[must_use]
+    pub fn always_clear(self, always_clear: bool) -> Self {
+        Self {
+            always_clear,
+            ..self
+        }
+    }
+
+    /// Sets the color the window is cleared with (gray by default).
+    ///
+    /// With a transparent window (see
+    /// [`transparent_window()`](Self::transparent_window())), its alpha
+    /// makes the desktop show through where nothing else is drawn.
+    #[must_use]
+    pub fn background_color(self, clear_color: Color) -> Self {
+        Self {
+            clear_color,
+            ..self
+        }
+    }
+
+    /// Makes the window transparent where the background is, e.g. to
+    /// show the automaton over the desktop (see
+    /// [`background_color()`](Self::background_color())).  The window is
+    /// cleared every frame then (see
+    /// [`always_clear()`](Self::always_clear())).
+    ///
+    /// Platforms that don't support transparent windows show an opaque
+    /// one instead.
+    #[must_use]
+    pub fn transparent_window(self, transparent: bool) -> Self {
+        Self {
+            window_flags: WindowFlags {
+                transparent,
+                ..self.window_flags
+            },
+            ..self
+        }
+    }
+
+    /// Removes the title bar and the borders of the window.
+    #[must_use]
+    pub fn undecorated(self, undecorated: bool) -> Self {
+        Self {
+            window_flags: WindowFlags {
+                undecorated,
+                ..self.window_flags
+            },
+            ..self
+        }
+    }
+
+    /// Keeps the window above the other ones (see
+    /// [`RaylibFrontend::set_always_on_top()`]).
+    #[must_use]
+    pub fn always_on_top(self, always_on_top: bool) -> Self {
+        Self {
+            window_flags: WindowFlags {
+                always_on_top,
+                ..self.window_flags
+            },
+            ..self
+        }
+    }
+
+    /// Lets the mouse clicks go to the windows underneath (see
+    /// [`RaylibFrontend::set_click_through()`]).
+    #[must_use]
+    pub fn click_through(self, click_through: bool) -> Self {
+        Self {
+            window_flags: WindowFlags {
+                click_through,
+                ..self.window_flags
+            },
+            ..self
+        }
+    }
+
+    /// Sets the entries of the color legend (see
+    /// [`RaylibFrontend::set_legend()`]).
+    #[must_use]
+    pub fn legend<L: Into<String>>(self, entries: impl IntoIterator<Item = (L, Color)>) -> Self {
+        Self {
+            legend: entries
+                .into_iter()
+                .map(|(label, color)| (label.into(), color))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Sets the corner of the window the color legend is drawn in.
+    #[must_use]
+    pub fn legend_corner(self, legend_corner: Corner) -> Self {
+        Self {
+            legend_corner,
+            ..self
+        }
+    }
+
+    /// Sets how the margin between cells reacts to zooming (see
+    /// [`MarginMode`]).
+    #[must_use]
+    pub fn margin_mode(self, margin_mode: MarginMode) -> Self {
+        Self {
+            margin_mode,
+            ..self
+        }
+    }
+
+    /// Draws the grid as a texture with a single pixel per cell,
+    /// scaled to the window.
+    ///
+    /// This is a lot faster for large grids, but the cell margin is
+    /// ignored.
+    #[must_use]
+    pub fn pixel_mode(self, pixel_mode: bool) -> Self {
+        Self { pixel_mode, ..self }
+    }
+
+    /// Sets the sides of the cells on the screen (in pixels) below which
+    /// the margins between them are dropped, and below which the grid
+    /// is drawn as in pixel mode (see [`pixel_mode()`](Self::pixel_mode())),
+    /// `3.0` and `1.5` by default.
+    ///
+    /// Tiny cells with margins look like noise and cost more to draw.
+    /// `0.0` keeps the respective details at any size.  The current
+    /// level is shown along with the timings in the HUD (see
+    /// [`RaylibFrontend::detail_level()`]).
+    #[must_use]
+    pub fn detail_thresholds(self, no_margins_below: f32, pixels_below: f32) -> Self {
+        Self {
+            detail_thresholds: (no_margins_below, pixels_below),
+            ..self
+        }
+    }
+
+    /// Sets the time a frame should take at least (1/60 s by default),
+    /// sleeping at the end of [`RaylibFrontend::display_grid()`] for the
+    /// rest of it, or [`None`] to draw frames as fast as possible.
+    ///
+    /// Otherwise frames with nothing new to show keep a core busy,
+    /// unless vsync is on.  The sleep ends in time for the next step, and
+    /// is skipped while vsync (which paces the frames already) or turbo
+    /// mode is on, the mouse is being used, or the frontend is headless.
+    #[must_use]
+    pub fn frame_pacing(self, frame_time: Option<Duration>) -> Self {
+        Self {
+            frame_pacing: frame_time,
+            ..self
+        }
+    }
+
+    /// Keeps the drawn grid in a texture (off by default), so that the
+    /// frames between generations copy it to the window instead of
+    /// drawing every cell again.  The HUD and the overlays are still
+    /// drawn over it every frame.
+    ///
+    /// The grid is drawn again after a step, an edit, a resize, a move of
+    /// the view or a change of the colors through the frontend.  It's
+    /// off by default since colors changing on their own (e.g. given by
+    /// a closure reading some outside state) aren't noticed, these need
+    /// [`RaylibFrontend::invalidate_render_cache()`].  The texture covers
+    /// the whole window, so it's not used with a translucent
+    /// [`background_color()`](Self::background_color()).
+    ///
+    /// With vsync, frames are still presented at the display rate, so
+    /// this only saves the time spent drawing them.  Without it, cheaper
+    /// frames just make the loop spin faster, unless the frame rate is
+    /// limited (see [`RaylibFrontend::raylib_handle()`]).
+    #[must_use]
+    pub fn cache_grid(self, cache_grid: bool) -> Self {
+        Self { cache_grid, ..self }
+    }
+
+    /// Sets the filter used when the grid texture is scaled in pixel
+    /// mode.
+    ///
+    /// Defaults to [`TextureFilter::TEXTURE_FILTER_POINT`], which keeps
+    /// the cells sharp, while
+    /// [`TextureFilter::TEXTURE_FILTER_BILINEAR`] looks smoother on
+    /// non-integer scales.
+    #[must_use]
+    pub fn texture_filter(self, texture_filter: TextureFilter) -> Self {
+        Self {
+            texture_filter,
+            ..self
+        }
+    }
+
+    /// Sets the share of the grid texture (`0.5` by default) that may
+    /// change in a frame for only the changed part of it to be uploaded
+    /// to the GPU in pixel mode, instead of the whole texture.
+    ///
+    /// The changed part is the bounding rectangle of the changed cells,
+    /// so scattered changes upload more than they'd need to.  `0.0`
+    /// always uploads the whole texture.  The bytes uploaded are shown
+    /// with the timings in the HUD.
+    ///
+    /// It's clamped to `0.0..=1.0`, and NaN is replaced with the
+    /// default.
+    #[must_use]
+    pub fn partial_upload_threshold(self, partial_upload_threshold: f32) -> Self {
+        Self {
+            partial_upload_threshold,
+            ..self
+        }
+    }
+
+    /// Shows the generation counter.
+    #[must_use]
+    pub fn show_generation(self, show_generation: bool) -> Self {
+        Self {
+            show_generation,
+            ..self
+        }
+    }
+
+    /// Shows the FPS counter along with the frame time.
+    ///
+    /// It can also be toggled with [`RaylibFrontend::toggle_fps()`].
+    #[must_use]
+    pub fn show_fps(self, show_fps: bool) -> Self {
+        Self { show_fps, ..self }
+    }
+
+    /// Shows the number of live cells.
+    ///
+    /// The count is also available through
+    /// [`RaylibFrontend::population()`].
+    #[must_use]
+    pub fn show_population(self, show_population: bool) -> Self {
+        Self {
+            show_population,
+            ..self
+        }
+    }
+
+    /// Shows the configured update rate, along with the rate
+    /// measured over the last second.
+    #[must_use]
+    pub fn show_rate(self, show_rate: bool) -> Self {
+        Self { show_rate, ..self }
+    }
+
+    /// Shows a badge while the simulation is paused (enabled by
+    /// default).
+    #[must_use]
+    pub fn show_paused(self, show_paused: bool) -> Self {
+        Self {
+            show_paused,
+            ..self
+        }
+    }
+
+    /// Sets the text of the badge shown while paused.
+    #[must_use]
+    pub fn paused_text(self, paused_text: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            paused_text: paused_text.into(),
+            ..self
+        }
+    }
+
+    /// Sets the appearance of the HUD elements (counters, legend,
+    /// etc.).
+    #[must_use]
+    pub fn hud_style(self, hud_style: HudStyle) -> Self {
+        Self { hud_style, ..self }
+    }
+
+    /// Sets the corner of the window the counters are drawn in.
+    #[must_use]
+    pub fn hud_corner(self, hud_corner: Corner) -> Self {
+        Self { hud_corner, ..self }
+    }
+
+    /// Shows a status bar along an edge of the window (see
+    /// [`StatusBar`]).
+    #[must_use]
+    pub fn status_bar(self, status_bar: StatusBar) -> Self {
+        Self {
+            status_bar: Some(status_bar),
+            ..self
+        }
+    }
+
+    /// Shows a rolling graph of a statistic over the last generations,
+    /// the population by default (see
+    /// [`RaylibFrontend::set_graph_source()`]).
+    #[must_use]
+    pub fn show_graph(self, show_graph: bool) -> Self {
+        Self { show_graph, ..self }
+    }
+
+    /// Sets the number of generations shown by the graph (300 by
+    /// default).
+    #[must_use]
+    pub fn graph_length(self, graph_length: usize) -> Self {
+        Self {
+            graph_length,
+            ..self
+        }
+    }
+
+    /// Sets the statistic shown by the graph (see
+    /// [`RaylibFrontend::set_graph_series()`]).
+    #[must_use]
+    pub fn graph_series(self, graph_series: GraphSeries) -> Self {
+        Self {
+            graph_series,
+            ..self
+        }
+    }
+
+    /// Counts the cells born and died in each step (see
+    /// [`RaylibFrontend::last_step_stats()`]).
+    ///
+    /// It's enabled automatically if the status bar or the graph
+    /// shows these statistics, otherwise the cost of comparing the
+    /// cells before and after each step is avoided.
+    #[must_use]
+    pub fn track_step_stats(self, track_step_stats: bool) -> Self {
+        Self {
+            track_step_stats,
+            ..self
+        }
+    }
+
+    /// Records the positions of the cells born and died in each step
+    /// (see [`RaylibFrontend::last_diff()`]).
+    ///
+    /// Off by default, as it takes comparing the cells before and after
+    /// each step.
+    #[must_use]
+    pub fn track_diff(self, track_diff: bool) -> Self {
+        Self { track_diff, ..self }
+    }
+
+    /// Sets the corner of the window the graph is drawn in.
+    #[must_use]
+    pub fn graph_corner(self, graph_corner: Corner) -> Self {
+        Self {
+            graph_corner,
+            ..self
+        }
+    }
+
+    /// Sets the number of toasts shown at once (see
+    /// [`RaylibFrontend::toast()`]).
+    #[must_use]
+    pub fn max_toasts(self, max_toasts: usize) -> Self {
+        Self { max_toasts, ..self }
+    }
+
+    /// Sets the appearance of the toasts.
+    #[must_use]
+    pub fn toast_style(self, toast_style: HudStyle) -> Self {
+        Self {
+            toast_style,
+            ..self
+        }
+    }
+
+    /// Sets the corner of the window the toasts are drawn in.
+    #[must_use]
+    pub fn toast_corner(self, toast_corner: Corner) -> Self {
+        Self {
+            toast_corner,
+            ..self
+        }
+    }
+
+    /// Sets the font used for all the text, loaded from the given file
+    /// once the window is created (see
+    /// [`RaylibFrontend::load_font()`]).
+    ///
+    /// If it can't be loaded, the default font is used and a toast
+    /// with the error is shown, unless the frontend is created with
+    /// [`try_finish()`](Self::try_finish()).
+    #[must_use]
+    pub fn font(self, path: impl Into<String>, size: i32) -> Self {
+        Self {
+            font: Some((path.into(), size)),
+            ..self
+        }
+    }
+
+    /// Stops the simulation once the given number of generations is
+    /// computed, or lets it run indefinitely if [`None`] is given (see
+    /// [`RaylibFrontend::set_max_generations()`]).
+    #[must_use]
+    pub fn max_generations(self, max_generations: Option<u64>) -> Self {
+        Self {
+            max_generations,
+            ..self
+        }
+    }
+
+    /// Pauses the simulation once a step doesn't change anything (e.g.
+    /// the automaton settled into a still life).
+    ///
+    /// See [`pause_on_period()`](Self::pause_on_period()) for
+    /// oscillators.
+    #[must_use]
+    pub fn pause_on_stable(self, pause_on_stable: bool) -> Self {
+        Self {
+            pause_period: if pause_on_stable {
+                self.pause_period.max(1)
+            } else {
+                0
+            },
+            period_pauses: true,
+            ..self
+        }
+    }
+
+    /// Pauses the simulation once the automaton repeats any of the last
+    /// `max_period` states, which also catches oscillators with periods
+    /// up to `max_period`.
+    ///
+    /// The last `max_period` states are kept in memory, but only if
+    /// this is enabled (`0` disables it).
+    #[must_use]
+    pub fn pause_on_period(self, max_period: usize) -> Self {
+        Self {
+            pause_period: max_period,
+            period_pauses: true,
+            ..self
+        }
+    }
+
+    /// Like [`pause_on_period()`](Self::pause_on_period()), but only
+    /// reports the cycles (see [`RaylibFrontend::set_on_cycle()`])
+    /// without pausing.
+    #[must_use]
+    pub fn detect_period(self, max_period: usize) -> Self {
+        Self {
+            pause_period: max_period,
+            period_pauses: false,
+            ..self
+        }
+    }
+
+    /// Makes [`RaylibFrontend::window_should_close()`] return `true`
+    /// once the generation limit is reached (see
+    /// [`max_generations()`](Self::max_generations())).
+    #[must_use]
+    pub fn close_on_finish(self, close_on_finish: bool) -> Self {
+        Self {
+            close_on_finish,
+            ..self
+        }
+    }
+
+    /// Shows a thin bar along the bottom edge of the window with the
+    /// progress towards [`max_generations()`](Self::max_generations())
+    /// (enabled by default).
+    #[must_use]
+    pub fn show_progress(self, show_progress: bool) -> Self {
+        Self {
+            show_progress,
+            ..self
+        }
+    }
+
+    /// Sets the window title.
+    #[must_use]
+    pub fn window_title(self, window_title: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            window_title: window_title.into(),
+            ..self
+        }
+    }
+
+    /// Shows the generation and the population in the window title,
+    /// after the title itself (see
+    /// [`window_title()`](Self::window_title())).
+    ///
+    /// It's only updated when the automaton steps.
+    #[must_use]
+    pub fn title_stats(self, title_stats: bool) -> Self {
+        Self {
+            title_format: title_stats.then_some(Cow::Borrowed(DEFAULT_TITLE_FORMAT)),
+            ..self
+        }
+    }
+
+    /// Shows statistics in the window title in the given format (see
+    /// [`title_stats()`](Self::title_stats())).
+    ///
+    /// `{gen}`, `{pop}` and `{rate}` are replaced with the generation,
+    /// the population and the measured update rate respectively.
+    #[must_use]
+    pub fn title_format(self, title_format: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            title_format: Some(title_format.into()),
+            ..self
+        }
+    }
+
+    /// Converts the builder to an actual [`RaylibFrontend`].
+    pub fn finish<S, D>(self, automaton: Automaton<S, D>) -> RaylibFrontend<S, D> {
+        let initial_pattern = self.initial_pattern.is_some();
+        let mut frontend = RaylibFrontend::with_window(
+            automaton,
+            self.init_grid_size,
+            self.background
+                .unwrap_or(Background::Solid(self.default_color)),
+            self.update_rate,
+            if self.pixel_mode { 0 } else { self.cell_margin },
+            self.window_flags.open(self.window_size),
+            self.partial_cells,
+        );
+
+        frontend.always_clear = self.always_clear || self.window_flags.transparent;
+        frontend.clear_color = self.clear_color;
+        frontend.margin_mode = self.margin_mode;
+        frontend.legend.entries = self.legend;
+        frontend.legend.corner = self.legend_corner;
+        frontend.pixel_mode = self.pixel_mode;
+        frontend.grid_cache.enabled = self.cache_grid;
+        frontend.pacer.target = self.frame_pacing;
+        frontend.detail_thresholds = self.detail_thresholds;
+        frontend.invalidate_layout();
+        frontend.shell.hud.style = self.hud_style;
+        frontend.shell.hud.corner = self.hud_corner;
+        frontend.shell.hud.show_generation = self.show_generation;
+        frontend.shell.hud.show_fps = self.show_fps;
+        frontend.shell.hud.show_population = self.show_population;
+        frontend.shell.hud.show_rate = self.show_rate;
+        frontend.shell.hud.paused_text = self.show_paused.then_some(self.paused_text);
+        frontend.canvas = PixelCanvas::new(self.texture_filter);
+        frontend
+            .canvas
+            .set_partial_upload_threshold(self.partial_upload_threshold);
+        if let Some((path, size)) = &self.font {
+            if let Err(error) = frontend.load_font(path, *size) {
+                frontend.toast(format!("Couldn't load the font: {error}"));
+            }
+        }
+        frontend.shell.title = self.window_title;
+        frontend.shell.title_format = self.title_format;
+        frontend.update_title();
+        frontend.timer.set_paused(self.start_paused);
+        frontend.shell.hud.rate_unit = self.rate_unit;
+        frontend.max_steps_per_frame = self.max_steps_per_frame;
+        frontend.set_render_every(self.render_every);
+        frontend.shell.pause_on_focus_loss = self.pause_on_focus_loss;
+        frontend.shell.simulate_when_minimized = self.simulate_when_minimized;
+        if self.headless {
+            frontend.hide_window();
+        }
+        frontend.set_cursor_hidden(self.hide_cursor);
+        frontend.quick_save_keys = self.quick_save_keys;
+        #[cfg(feature = "gif")]
+        {
+            frontend.gif_key = self.gif_key;
+        }
+        #[cfg(feature = "serde")]
+        {
+            frontend.session_path = self.session_path;
+        }
+        frontend.timer.set_max_elapsed(self.max_frame_time);
+        frontend.shell.turbo_budget = self.turbo_budget;
+        if self.dpi_scale.is_some() {
+            frontend.set_dpi_scale(self.dpi_scale);
+        }
+        frontend.max_generations = self.max_generations;
+        frontend.close_on_finish = self.close_on_finish;
+        if self.period_pauses {
+            frontend.set_pause_on_period(self.pause_period);
+        } else {
+            frontend.set_detect_period(self.pause_period);
+        }
+        frontend.shell.hud.show_progress = self.show_progress;
+        frontend.shell.toasts.max = self.max_toasts;
+        frontend.shell.toasts.style = self.toast_style;
+        frontend.shell.toasts.corner = self.toast_corner;
+        frontend.set_turbo(self.turbo);
+        frontend.graph_series = self.graph_series;
+        frontend.track_step_stats = self.track_step_stats;
+        frontend.track_diff = self.track_diff;
+        frontend.graph = self.show_graph.then(|| {
+            let mut graph = Graph::new(self.graph_length);
+            graph.corner = self.graph_corner;
+
+            graph
+        });
+        if self.status_bar.is_some() {
+            frontend.set_status_bar(self.status_bar);
+        }
+
+        if initial_pattern {
+            frontend.toast("The initial pattern is only placed by `finish_with_pattern()`");
+        }
+
+        frontend
+    }
+
+    /// Like [`finish()`](Self::finish()), but also places the initial
+    /// pattern (see [`initial_pattern()`](Self::initial_pattern())) in
+    /// the middle of the grid, making its alive cells with `cell` (see
+    /// [`RaylibFrontend::place_centered()`]).
+    ///
+    /// Fails if the pattern can't be read or parsed, before the window
+    /// is opened.
+    pub fn finish_with_pattern<S, D>(
+        mut self,
+        automaton: Automaton<S, D>,
+        cell: impl FnMut() -> S,
+    ) -> Result<RaylibFrontend<S, D>, RleError> {
+        let pattern = self
+            .initial_pattern
+            .take()
+            .map(PatternSource::load)
+            .transpose()?;
+        let mut frontend = self.finish(automaton);
+
+        if let Some(pattern) = pattern {
+            frontend.place_centered(&pattern, cell);
+        }
+
+        Ok(frontend)
+    }
+
+    /// Like [`finish()`](Self::finish()), but fails if the font can't
+    /// be loaded.
+    pub fn try_finish<S, D>(
+        mut self,
+        automaton: Automaton<S, D>,
+    ) -> Result<RaylibFrontend<S, D>, String> {
+        let font = self.font.take();
+        let mut frontend = self.finish(automaton);
+
+        if let Some((path, size)) = font {
+            frontend.load_font(&path, size)?;
+        }
+
+        Ok(frontend)
+    }
+}
+
+impl Default for FrontendBuilder {
+    fn default() -> Self {
+        Self {
+            window_size: (1024, 768),
+            cell_margin: 5,
+            update_rate: Duration::from_millis(100),
+            init_grid_size: (10, 10),
+            default_color: Color::BLACK,
+            background: None,
+            partial_cells: false,
+            always_clear: false,
+            clear_color: Color::GRAY,
+            window_flags: WindowFlags {
+                transparent: false,
+                undecorated: false,
+                always_on_top: false,
+                click_through: false,
+            },
+            legend: Vec::new(),
+            legend_corner: Corner::TopRight,
+            pixel_mode: false,
+            detail_thresholds: DEFAULT_DETAIL_THRESHOLDS,
+            frame_pacing: Some(DEFAULT_FRAME_TIME),
+            cache_grid: false,
+            texture_filter: TextureFilter::TEXTURE_FILTER_POINT,
+            partial_upload_threshold: DEFAULT_PARTIAL_UPLOAD_THRESHOLD,
+            show_generation: false,
+            show_fps: false,
+            show_population: false,
+            show_rate: false,
+            show_paused: true,
+            paused_text: Cow::Borrowed(DEFAULT_PAUSED_TEXT),
+            hud_style: HudStyle::default(),
+            hud_corner: Corner::TopLeft,
+            margin_mode: MarginMode::default(),
+            status_bar: None,
+            show_graph: false,
+            graph_length: DEFAULT_GRAPH_LENGTH,
+            graph_corner: Corner::BottomLeft,
+            graph_series: GraphSeries::Population,
+            track_step_stats: false,
+            track_diff: false,
+            max_toasts: DEFAULT_MAX_TOASTS,
+            toast_style: HudStyle::new(),
+            toast_corner: Corner::BottomRight,
+            font: None,
+            max_generations: None,
+            show_progress: true,
+            window_title: Cow::Borrowed(DEFAULT_TITLE),
+            title_format: None,
+            start_paused: false,
+            rate_unit: RateUnit::MillisPerStep,
+            max_steps_per_frame: DEFAULT_MAX_CYCLES,
+            close_on_finish: false,
+            pause_period: 0,
+            period_pauses: true,
+            turbo_budget: DEFAULT_TURBO_BUDGET,
+            turbo: false,
+            dpi_scale: None,
+            max_frame_time: DEFAULT_MAX_ELAPSED,
+            render_every: 1,
+            pause_on_focus_loss: false,
+            simulate_when_minimized: true,
+            headless: false,
+            hide_cursor: false,
+            quick_save_keys: DEFAULT_QUICK_SAVE_KEYS,
+            #[cfg(feature = "gif")]
+            gif_key: DEFAULT_GIF_KEY,
+            #[cfg(feature = "serde")]
+            session_path: PathBuf::from(DEFAULT_SESSION_PATH),
+            initial_pattern: None,
         }
     }
 }
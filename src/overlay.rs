@@ -0,0 +1,67 @@
+//! User-defined drawing on top of the grid.
+
+use raylib::{
+    drawing::RaylibDrawHandle,
+    math::{Rectangle, Vector2},
+};
+
+/// A callback drawing on top of the grid each frame.
+///
+/// See [`generic::RaylibFrontend::add_overlay()`](crate::generic::RaylibFrontend::add_overlay()).
+pub type Overlay = Box<dyn FnMut(&mut RaylibDrawHandle<'_>, &OverlayContext)>;
+
+/// Layout of the grid on the screen, passed to overlays so that they
+/// can align to cells.
+#[derive(Clone, Copy, Debug)]
+pub struct OverlayContext {
+    /// Screen area taken up by the grid.
+    pub grid_rect: Rectangle,
+    /// Screen position of the top-left corner of the cell at `(0, 0)`.
+    ///
+    /// It may lie outside of [`grid_rect`](Self::grid_rect) if the view
+    /// is scrolled.
+    pub origin: Vector2,
+    /// Side length of a single cell.
+    pub cell_size: f32,
+    /// Gap between adjacent cells.
+    pub cell_margin: f32,
+}
+
+impl OverlayContext {
+    /// Returns the screen rectangle of the cell at the given position.
+    #[allow(clippy::as_conversions)]
+    pub fn cell_rect(&self, (x, y): (usize, usize)) -> Rectangle {
+        let pitch = self.cell_size + self.cell_margin;
+
+        Rectangle::new(
+            (x as f32).mul_add(pitch, self.origin.x),
+            (y as f32).mul_add(pitch, self.origin.y),
+            self.cell_size,
+            self.cell_size,
+        )
+    }
+
+    /// Returns the position of the cell at the given screen point, or
+    /// [`None`] if it's outside of the grid area, or between cells.
+    ///
+    /// This is the inverse of [`cell_rect()`](Self::cell_rect()).
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn cell_at(&self, point: Vector2) -> Option<(usize, usize)> {
+        let pitch = self.cell_size + self.cell_margin;
+        let (x, y) = (point.x - self.origin.x, point.y - self.origin.y);
+
+        if !self.grid_rect.check_collision_point_rec(point) || x < 0. || y < 0. || pitch <= 0. {
+            return None;
+        }
+
+        let pos = ((x / pitch).floor() as usize, (y / pitch).floor() as usize);
+
+        self.cell_rect(pos)
+            .check_collision_point_rec(point)
+            .then_some(pos)
+    }
+}
@@ -0,0 +1,65 @@
+//! Limiting the frame rate by sleeping, so that frames with nothing new
+//! to show don't keep the CPU busy.
+
+#[cfg(not(all(feature = "web", target_os = "emscripten")))]
+use std::thread;
+use std::time::Duration;
+
+use crate::clock::Instant;
+
+/// The default time a frame should take (60 frames per second).
+pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+/// How long before the deadline the pacer stops sleeping and spins
+/// instead, as sleeping tends to overshoot.
+#[cfg(not(all(feature = "web", target_os = "emscripten")))]
+const SPIN_MARGIN: Duration = Duration::from_millis(1);
+
+/// Waits at the end of each frame until it took the target time.
+pub(crate) struct FramePacer {
+    /// The time a frame should take, or [`None`] not to wait at all.
+    pub target: Option<Duration>,
+    last_frame: Instant,
+}
+
+impl FramePacer {
+    /// Creates a pacer aiming for frames of the given length.
+    pub fn new(target: Option<Duration>) -> Self {
+        Self {
+            target,
+            last_frame: Instant::now(),
+        }
+    }
+
+    /// Waits until the target time has passed since the end of the
+    /// last frame, but no longer than `deadline` (e.g. the time until
+    /// the next step), unless `skip` is set.
+    pub fn end_frame(&mut self, deadline: Option<Duration>, skip: bool) {
+        if let (Some(target), false) = (self.target, skip) {
+            let remaining = target.saturating_sub(self.last_frame.elapsed());
+
+            wait(deadline.map_or(remaining, |deadline| remaining.min(deadline)));
+        }
+
+        self.last_frame = Instant::now();
+    }
+}
+
+/// Sleeps for most of the given time and spins for the rest, to wake
+/// up on time.
+#[cfg(not(all(feature = "web", target_os = "emscripten")))]
+fn wait(duration: Duration) {
+    let start = Instant::now();
+
+    if let Some(sleep) = duration.checked_sub(SPIN_MARGIN) {
+        thread::sleep(sleep);
+    }
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+/// The browser paces the frames itself, and blocking would freeze the
+/// page.
+#[cfg(all(feature = "web", target_os = "emscripten"))]
+const fn wait(_: Duration) {}
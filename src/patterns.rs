@@ -0,0 +1,704 @@
+//! Loading and saving of patterns in common file formats.
+//!
+//! The run length encoded format (RLE) used by Golly and the LifeWiki,
+//! the plaintext format (`.cells`) and the Life 1.06 format are
+//! supported, both for reading and writing:
+//!
+//! ```ignore
+//! # use lifers_raylib::patterns;
+//! let glider = patterns::parse_rle("x = 3, y = 3\nbo$2bo$3o!")?;
+//!
+//! assert_eq!(glider.cells.len(), 5);
+//! ```
+
+use std::{
+    env,
+    error::Error,
+    fmt,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The first line of a pattern in the Life 1.06 format.
+const LIFE_106_HEADER: &str = "#Life 1.06";
+
+/// The longest line written by [`to_rle()`].
+const RLE_LINE_LENGTH: usize = 70;
+
+/// A pattern of alive cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Pattern {
+    /// Width of the pattern's bounding box.
+    pub width: usize,
+    /// Height of the pattern's bounding box.
+    pub height: usize,
+    /// The rule the pattern is meant for (e.g. `B3/S23`), if given.
+    pub rule: Option<String>,
+    /// The name of the pattern, if given (`#N` line).
+    pub name: Option<String>,
+    /// Positions of the alive cells, relative to the top-left corner.
+    pub cells: Vec<(usize, usize)>,
+    /// How far the cells were moved to make their positions
+    /// non-negative (only in the Life 1.06 format).
+    pub offset: (isize, isize),
+}
+
+impl Pattern {
+    /// Creates a pattern from the positions of the alive cells, moving
+    /// them so that their bounding box starts at `(0, 0)`.
+    pub fn from_cells(cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut cells: Vec<_> = cells.into_iter().collect();
+        let Some(((min_x, min_y), (max_x, max_y))) = cells.iter().fold(
+            None::<((usize, usize), (usize, usize))>,
+            |bounds, &(x, y)| {
+                Some(
+                    bounds.map_or(((x, y), (x, y)), |((min_x, min_y), (max_x, max_y))| {
+                        ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+                    }),
+                )
+            },
+        ) else {
+            return Self::default();
+        };
+
+        cells
+            .iter_mut()
+            .for_each(|(x, y)| (*x, *y) = (x.saturating_sub(min_x), y.saturating_sub(min_y)));
+        cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+
+        Self {
+            width: max_x.saturating_sub(min_x).saturating_add(1),
+            height: max_y.saturating_sub(min_y).saturating_add(1),
+            cells,
+            ..Self::default()
+        }
+    }
+}
+
+/// An error while loading a pattern.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RleError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// There's no header line (e.g. `x = m, y = n`).
+    MissingHeader,
+    /// The header line is malformed.
+    InvalidHeader(String),
+    /// A run count is zero or too large.
+    InvalidCount(String),
+    /// A line of the Life 1.06 format isn't a pair of coordinates.
+    InvalidCoordinates(String),
+    /// A character other than `b`, `o`, `$`, `!`, digits or whitespace
+    /// (or `.` and `O` in the plaintext format) was found in the pattern
+    /// data.
+    UnexpectedChar {
+        /// The character found.
+        found: char,
+        /// Line it was found on (starting from 1).
+        line: usize,
+    },
+    /// The pattern data ends without the terminating `!`.
+    Truncated,
+    /// A cell lies outside of the size given in the header.
+    OutOfBounds {
+        /// Column of the cell.
+        x: usize,
+        /// Row of the cell.
+        y: usize,
+    },
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "couldn't read the pattern: {error}"),
+            Self::MissingHeader => write!(f, "missing header line"),
+            Self::InvalidHeader(header) => write!(f, "invalid header line: `{header}`"),
+            Self::InvalidCount(count) => write!(f, "invalid run count: `{count}`"),
+            Self::InvalidCoordinates(line) => write!(f, "invalid coordinates: `{line}`"),
+            Self::UnexpectedChar { found, line } => {
+                write!(f, "unexpected character `{found}` on line {line}")
+            }
+            Self::Truncated => write!(f, "pattern data ends without `!`"),
+            Self::OutOfBounds { x, y } => {
+                write!(f, "cell at ({x}, {y}) lies outside of the declared size")
+            }
+        }
+    }
+}
+
+impl Error for RleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Where to get a pattern from, e.g. to start with (see
+/// [`generic::FrontendBuilder::initial_pattern()`](crate::generic::FrontendBuilder::initial_pattern())).
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum PatternSource {
+    /// A file in any of the supported formats (see [`load_pattern()`]).
+    Path(PathBuf),
+    /// A pattern in the RLE format.
+    Rle(&'static str),
+    /// A pattern parsed already.
+    Pattern(Pattern),
+}
+
+impl PatternSource {
+    /// Takes the first command line argument as the path to a pattern
+    /// file, if there is one.
+    pub fn from_args() -> Option<Self> {
+        env::args_os().nth(1).map(|path| Self::Path(path.into()))
+    }
+
+    /// Reads or parses the pattern.
+    pub fn load(self) -> Result<Pattern, RleError> {
+        match self {
+            Self::Path(path) => load_pattern(path),
+            Self::Rle(text) => parse_rle(text),
+            Self::Pattern(pattern) => Ok(pattern),
+        }
+    }
+}
+
+/// Reads and parses an RLE pattern file (see [`parse_rle()`]).
+pub fn load_rle(path: impl AsRef<Path>) -> Result<Pattern, RleError> {
+    parse_rle(&fs::read_to_string(path)?)
+}
+
+/// Reads and parses a pattern file, in the Life 1.06 format if it
+/// starts with its header, in the plaintext format if its extension is
+/// `.cells` and in the RLE format otherwise.
+pub fn load_pattern(path: impl AsRef<Path>) -> Result<Pattern, RleError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+
+    if text.trim_start().starts_with(LIFE_106_HEADER) {
+        parse_life106(&text)
+    } else if path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("cells"))
+    {
+        parse_plaintext(&text)
+    } else {
+        parse_rle(&text)
+    }
+}
+
+/// Parses a pattern in the plaintext format, where each line is a row
+/// of dead (`.`) and alive (`O`) cells.
+///
+/// Comment lines (starting with `!`) are skipped, except for the name
+/// (`!Name:`).  Rows shorter than the others are padded with dead
+/// cells.
+pub fn parse_plaintext(text: &str) -> Result<Pattern, RleError> {
+    let mut pattern = Pattern::default();
+    let mut y = 0usize;
+
+    for (index, line) in text.lines().enumerate() {
+        if let Some(comment) = line.strip_prefix('!') {
+            if let Some(name) = comment.strip_prefix("Name:") {
+                pattern.name = Some(name.trim().to_owned());
+            }
+            continue;
+        }
+
+        let row = line.trim_end();
+
+        for (x, found) in row.chars().enumerate() {
+            match found {
+                '.' => {}
+                'O' => pattern.cells.push((x, y)),
+                _ => {
+                    return Err(RleError::UnexpectedChar {
+                        found,
+                        line: index.saturating_add(1),
+                    })
+                }
+            }
+        }
+
+        y = y.saturating_add(1);
+        if !row.is_empty() {
+            pattern.width = pattern.width.max(row.chars().count());
+            pattern.height = y;
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Parses a pattern in the Life 1.06 format, a header line followed by
+/// the coordinates (`x y`) of each alive cell.
+///
+/// If any of the coordinates are negative, all the cells are moved to
+/// make them non-negative, and the amount is stored in
+/// [`Pattern::offset`].
+pub fn parse_life106(text: &str) -> Result<Pattern, RleError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    if lines.next() != Some(LIFE_106_HEADER) {
+        return Err(RleError::MissingHeader);
+    }
+
+    let coordinates = lines
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let invalid = || RleError::InvalidCoordinates(line.to_owned());
+            let mut values = line.split_whitespace().map(str::parse::<isize>);
+
+            match (values.next(), values.next(), values.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => Ok((x, y)),
+                _ => Err(invalid()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (min_x, min_y) = coordinates.iter().fold((0, 0), |(min_x, min_y), &(x, y)| {
+        (min_x.min(x), min_y.min(y))
+    });
+    let offset = (min_x.saturating_neg(), min_y.saturating_neg());
+    let mut pattern = Pattern {
+        offset,
+        ..Pattern::default()
+    };
+
+    for (x, y) in coordinates {
+        let moved = |value: isize, by: isize| {
+            value
+                .checked_add(by)
+                .and_then(|value| usize::try_from(value).ok())
+                .ok_or_else(|| RleError::InvalidCoordinates(format!("{x} {y}")))
+        };
+        let (x, y) = (moved(x, offset.0)?, moved(y, offset.1)?);
+
+        pattern.width = pattern.width.max(x.saturating_add(1));
+        pattern.height = pattern.height.max(y.saturating_add(1));
+        pattern.cells.push((x, y));
+    }
+
+    Ok(pattern)
+}
+
+/// Parses a pattern in the RLE format.
+///
+/// Comment lines (starting with `#`) are skipped, except for the name
+/// (`#N`).  The pattern data may be split across any number of lines.
+pub fn parse_rle(text: &str) -> Result<Pattern, RleError> {
+    let mut pattern = Pattern::default();
+    let mut lines = text.lines().enumerate();
+
+    let header = loop {
+        let Some((_, line)) = lines.next() else {
+            return Err(RleError::MissingHeader);
+        };
+        let line = line.trim();
+
+        if let Some(name) = line.strip_prefix("#N") {
+            pattern.name = Some(name.trim().to_owned());
+        } else if !line.is_empty() && !line.starts_with('#') {
+            break line;
+        }
+    };
+
+    parse_header(header, &mut pattern)?;
+
+    let (mut x, mut y) = (0usize, 0usize);
+    let mut count = String::new();
+
+    for (index, line) in lines {
+        for found in line.chars() {
+            if found.is_ascii_digit() {
+                count.push(found);
+                continue;
+            }
+            if found.is_whitespace() {
+                continue;
+            }
+
+            let run = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&run| run > 0)
+                    .ok_or_else(|| RleError::InvalidCount(count.clone()))?
+            };
+            count.clear();
+
+            match found {
+                'b' => x = x.saturating_add(run),
+                'o' => {
+                    let end = x.saturating_add(run);
+
+                    if end > pattern.width || y >= pattern.height {
+                        return Err(RleError::OutOfBounds {
+                            x: end.saturating_sub(1),
+                            y,
+                        });
+                    }
+
+                    pattern.cells.extend((x..end).map(|x| (x, y)));
+                    x = end;
+                }
+                '$' => {
+                    x = 0;
+                    y = y.saturating_add(run);
+                }
+                '!' => return Ok(pattern),
+                _ => {
+                    return Err(RleError::UnexpectedChar {
+                        found,
+                        line: index.saturating_add(1),
+                    })
+                }
+            }
+        }
+    }
+
+    Err(RleError::Truncated)
+}
+
+/// Parses the header line (`x = m, y = n, rule = ...`) into the size
+/// and the rule of the pattern.
+fn parse_header(header: &str, pattern: &mut Pattern) -> Result<(), RleError> {
+    let invalid = || RleError::InvalidHeader(header.to_owned());
+    let (mut width, mut height) = (None, None);
+
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match key.trim() {
+            "x" => width = Some(value.parse().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse().map_err(|_| invalid())?),
+            "rule" => pattern.rule = Some(value.to_owned()),
+            _ => return Err(invalid()),
+        }
+    }
+
+    pattern.width = width.ok_or_else(invalid)?;
+    pattern.height = height.ok_or_else(invalid)?;
+
+    Ok(())
+}
+
+/// Encodes a pattern in the RLE format, with lines of at most 70
+/// characters.
+pub fn to_rle(pattern: &Pattern) -> String {
+    let mut cells = pattern.cells.clone();
+    let mut tokens = Vec::new();
+    let (mut x, mut y) = (0usize, 0usize);
+    let token = |count: usize, tag: char| {
+        if count == 1 {
+            tag.to_string()
+        } else {
+            format!("{count}{tag}")
+        }
+    };
+
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells.dedup();
+    cells
+        .chunk_by(|a, b| a.1 == b.1 && b.0 == a.0.saturating_add(1))
+        .for_each(|run| {
+            let Some(&(start, row)) = run.first() else {
+                return;
+            };
+
+            if row > y {
+                tokens.push(token(row.saturating_sub(y), '$'));
+                (x, y) = (0, row);
+            }
+            if start > x {
+                tokens.push(token(start.saturating_sub(x), 'b'));
+            }
+            tokens.push(token(run.len(), 'o'));
+            x = start.saturating_add(run.len());
+        });
+    tokens.push("!".to_owned());
+
+    let mut rle = String::new();
+
+    if let Some(name) = &pattern.name {
+        let _ = writeln!(rle, "#N {name}");
+    }
+    rle.push_str("#C generated by lifers-raylib\n");
+    let _ = write!(rle, "x = {}, y = {}", pattern.width, pattern.height);
+    if let Some(rule) = &pattern.rule {
+        let _ = write!(rle, ", rule = {rule}");
+    }
+    rle.push('\n');
+
+    let mut line_length = 0usize;
+
+    tokens.iter().for_each(|token| {
+        if line_length.saturating_add(token.len()) > RLE_LINE_LENGTH {
+            rle.push('\n');
+            line_length = 0;
+        }
+        rle.push_str(token);
+        line_length = line_length.saturating_add(token.len());
+    });
+    rle.push('\n');
+
+    rle
+}
+
+/// Encodes a pattern in the plaintext format.
+pub fn to_plaintext(pattern: &Pattern) -> String {
+    let mut rows = vec![vec!['.'; pattern.width]; pattern.height];
+    let mut text = String::new();
+
+    pattern.cells.iter().for_each(|&(x, y)| {
+        if let Some(cell) = rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = 'O';
+        }
+    });
+
+    if let Some(name) = &pattern.name {
+        let _ = writeln!(text, "!Name: {name}");
+    }
+    text.push_str("!generated by lifers-raylib\n");
+    rows.iter().for_each(|row| {
+        text.extend(row);
+        text.push('\n');
+    });
+
+    text
+}
+
+/// Encodes a pattern in the Life 1.06 format, moving the cells back by
+/// [`Pattern::offset`].
+///
+/// The cells are sorted (by row, then column), so the same pattern is
+/// always encoded the same way.
+pub fn to_life106(pattern: &Pattern) -> String {
+    let mut cells = pattern.cells.clone();
+    let mut text = format!("{LIFE_106_HEADER}\n");
+    let moved = |value: usize, by: isize| {
+        isize::try_from(value)
+            .unwrap_or(isize::MAX)
+            .saturating_sub(by)
+    };
+
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells.dedup();
+    cells.iter().for_each(|&(x, y)| {
+        let _ = writeln!(
+            text,
+            "{} {}",
+            moved(x, pattern.offset.0),
+            moved(y, pattern.offset.1)
+        );
+    });
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: [(usize, usize); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    #[test]
+    fn rle_with_comments() {
+        let pattern = parse_rle(include_str!("../tests/fixtures/glider.rle")).unwrap();
+
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+        assert_eq!(pattern.cells, GLIDER);
+    }
+
+    #[test]
+    fn rle_multi_line_runs() {
+        let pattern = parse_rle(include_str!("../tests/fixtures/multiline.rle")).unwrap();
+        let expected: Vec<_> = (0..12)
+            .map(|x| (x, 0))
+            .chain((1..11).map(|x| (x, 1)))
+            .chain((0..3).map(|x| (x, 3)))
+            .collect();
+
+        assert_eq!((pattern.width, pattern.height), (12, 4));
+        assert_eq!(pattern.cells, expected);
+    }
+
+    #[test]
+    fn rle_run_past_width() {
+        assert!(matches!(
+            parse_rle("x = 2, y = 2\n3o!"),
+            Err(RleError::OutOfBounds { x: 2, y: 0 })
+        ));
+    }
+
+    #[test]
+    fn rle_rows_past_height() {
+        assert!(matches!(
+            parse_rle("x = 2, y = 2\no2$o!"),
+            Err(RleError::OutOfBounds { x: 0, y: 2 })
+        ));
+    }
+
+    #[test]
+    fn rle_zero_count() {
+        assert!(matches!(
+            parse_rle("x = 2, y = 2\n0o!"),
+            Err(RleError::InvalidCount(count)) if count == "0"
+        ));
+    }
+
+    #[test]
+    fn rle_malformed() {
+        assert!(matches!(
+            parse_rle("#C only a comment"),
+            Err(RleError::MissingHeader)
+        ));
+        assert!(matches!(
+            parse_rle("x = 2, z = 2\no!"),
+            Err(RleError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            parse_rle("x = 2, y = 2\nbo\nx!"),
+            Err(RleError::UnexpectedChar {
+                found: 'x',
+                line: 3
+            })
+        ));
+        assert!(matches!(
+            parse_rle("x = 2, y = 2\nbo$o"),
+            Err(RleError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn plaintext_trailing_empty_line() {
+        let pattern = parse_plaintext(include_str!("../tests/fixtures/glider.cells")).unwrap();
+
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.cells, GLIDER);
+    }
+
+    #[test]
+    fn life106_negative_coordinates() {
+        let pattern = parse_life106(include_str!("../tests/fixtures/glider.lif")).unwrap();
+
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.offset, (1, 1));
+        assert_eq!(pattern.cells, GLIDER);
+    }
+
+    #[test]
+    fn life106_malformed() {
+        assert!(matches!(parse_life106("0 0"), Err(RleError::MissingHeader)));
+        assert!(matches!(
+            parse_life106("#Life 1.06\n0 0 0"),
+            Err(RleError::InvalidCoordinates(_))
+        ));
+    }
+
+    /// Checks that the pattern survives encoding and parsing, and that
+    /// encoding it again gives the same text.
+    fn assert_round_trip(
+        pattern: &Pattern,
+        encode: fn(&Pattern) -> String,
+        parse: fn(&str) -> Result<Pattern, RleError>,
+    ) {
+        let text = encode(pattern);
+        let parsed = parse(&text).unwrap();
+
+        assert_eq!(&parsed, pattern, "{text}");
+        assert_eq!(encode(&parsed), text);
+    }
+
+    #[test]
+    fn rle_round_trip() {
+        let glider = parse_rle(include_str!("../tests/fixtures/glider.rle")).unwrap();
+        let multiline = parse_rle(include_str!("../tests/fixtures/multiline.rle")).unwrap();
+        let single = Pattern::from_cells([(7, 3)]);
+        let rectangle = Pattern::from_cells((0..3).flat_map(|y| (0..5).map(move |x| (x, y))));
+
+        [glider, multiline, single, rectangle]
+            .iter()
+            .for_each(|pattern| assert_round_trip(pattern, to_rle, parse_rle));
+    }
+
+    #[test]
+    fn rle_header() {
+        let glider = parse_rle(include_str!("../tests/fixtures/glider.rle")).unwrap();
+        let text = to_rle(&glider);
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                "#N Glider",
+                "#C generated by lifers-raylib",
+                "x = 3, y = 3, rule = B3/S23",
+                "bo$2bo$3o!",
+            ]
+        );
+    }
+
+    #[test]
+    fn rle_line_wrapping() {
+        let checkerboard = Pattern::from_cells((0..4).flat_map(|y| {
+            (0..200)
+                .filter(move |x| (x ^ y) & 1 == 0)
+                .map(move |x| (x, y))
+        }));
+        let text = to_rle(&checkerboard);
+
+        assert!(text.lines().count() > 10, "{text}");
+        assert!(
+            text.lines().all(|line| line.len() <= RLE_LINE_LENGTH),
+            "{text}"
+        );
+        assert_round_trip(&checkerboard, to_rle, parse_rle);
+    }
+
+    #[test]
+    fn plaintext_round_trip() {
+        let glider = parse_plaintext(include_str!("../tests/fixtures/glider.cells")).unwrap();
+
+        assert_eq!(
+            to_plaintext(&glider),
+            "!Name: Glider\n!generated by lifers-raylib\n.O.\n..O\nOOO\n"
+        );
+        assert_round_trip(&glider, to_plaintext, parse_plaintext);
+        assert_round_trip(
+            &Pattern::from_cells([(0, 0)]),
+            to_plaintext,
+            parse_plaintext,
+        );
+    }
+
+    #[test]
+    fn life106_round_trip() {
+        let glider = parse_life106(include_str!("../tests/fixtures/glider.lif")).unwrap();
+
+        assert_eq!(
+            to_life106(&glider),
+            "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n"
+        );
+        assert_round_trip(&glider, to_life106, parse_life106);
+    }
+}
@@ -0,0 +1,227 @@
+//! Rendering of the grid as a texture with a single pixel per cell.
+//!
+//! This is much cheaper than drawing each cell separately, but it can't
+//! show margins between cells.  Only the part of the texture that
+//! changed since the last frame is uploaded to the GPU, unless most of
+//! it did.
+//!
+//! The changed part is found by comparing the colors set with the ones
+//! already in the texture rather than from the generation diff, as the
+//! colors of surviving cells can change as well, and the texture only
+//! covers the visible part of the grid, which moves when panning.
+
+use raylib::{
+    color::Color,
+    consts::TextureFilter,
+    drawing::RaylibDraw,
+    math::{Rectangle, Vector2},
+    texture::{Image, RaylibTexture2D, Texture2D},
+    RaylibHandle, RaylibThread,
+};
+
+/// Bytes per pixel (RGBA).
+const PIXEL_SIZE: usize = 4;
+/// The default share of the texture above which the whole of it is
+/// uploaded instead of the changed part (see
+/// [`PixelCanvas::set_partial_upload_threshold()`]).
+pub const DEFAULT_PARTIAL_UPLOAD_THRESHOLD: f32 = 0.5;
+
+/// A texture with a pixel per cell, along with its contents on the
+/// CPU side.
+pub(crate) struct PixelCanvas {
+    size: (usize, usize),
+    pixels: Vec<u8>,
+    texture: Option<Texture2D>,
+    filter: TextureFilter,
+    /// The smallest and the largest positions of the pixels changed
+    /// since the last upload.
+    dirty: Option<((usize, usize), (usize, usize))>,
+    /// The share of the texture above which the whole of it is uploaded
+    /// instead of the changed part.
+    partial_upload_threshold: f32,
+    /// The changed part of the pixels, reused across uploads.
+    changed: Vec<u8>,
+}
+
+impl PixelCanvas {
+    /// Creates an empty canvas, the texture is only created on the
+    /// first [`prepare()`](Self::prepare()).
+    pub const fn new(filter: TextureFilter) -> Self {
+        Self {
+            size: (0, 0),
+            pixels: Vec::new(),
+            texture: None,
+            filter,
+            dirty: None,
+            partial_upload_threshold: DEFAULT_PARTIAL_UPLOAD_THRESHOLD,
+            changed: Vec::new(),
+        }
+    }
+
+    /// Sets the share of the texture above which the whole of it is
+    /// uploaded instead of the changed part.
+    ///
+    /// It's clamped to `0.0..=1.0`, and NaN is replaced with
+    /// [`DEFAULT_PARTIAL_UPLOAD_THRESHOLD`].
+    pub const fn set_partial_upload_threshold(&mut self, partial_upload_threshold: f32) {
+        self.partial_upload_threshold = valid_threshold(partial_upload_threshold);
+    }
+
+    /// Returns the filter used when the texture is scaled.
+    pub const fn filter(&self) -> TextureFilter {
+        self.filter
+    }
+
+    /// Sets the filter used when the texture is scaled.
+    ///
+    /// It's kept when the texture is recreated.
+    pub fn set_filter(&mut self, thread: &RaylibThread, filter: TextureFilter) {
+        self.filter = filter;
+
+        if let Some(texture) = &mut self.texture {
+            texture.set_texture_filter(thread, filter);
+        }
+    }
+
+    /// Makes sure the texture fits a grid of the given size, recreating
+    /// it if necessary.
+    pub fn prepare(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        size: (usize, usize),
+    ) -> Result<(), String> {
+        if self.texture.is_some() && self.size == size {
+            return Ok(());
+        }
+
+        let width = i32::try_from(size.0).map_err(|e| e.to_string())?;
+        let height = i32::try_from(size.1).map_err(|e| e.to_string())?;
+        let image = Image::gen_image_color(width.max(1), height.max(1), Color::BLANK);
+        let mut texture = rl.load_texture_from_image(thread, &image)?;
+
+        texture.set_texture_filter(thread, self.filter);
+
+        self.size = size;
+        self.pixels.clear();
+        self.pixels
+            .resize(size.0.saturating_mul(size.1).saturating_mul(PIXEL_SIZE), 0);
+        self.texture = Some(texture);
+        self.dirty = (size.0 > 0 && size.1 > 0)
+            .then(|| ((0, 0), (size.0.saturating_sub(1), size.1.saturating_sub(1))));
+
+        Ok(())
+    }
+
+    /// Sets the color of the pixel at the given position.
+    ///
+    /// Positions outside of the canvas are ignored.
+    pub fn set(&mut self, (x, y): (usize, usize), color: Color) {
+        if x >= self.size.0 || y >= self.size.1 {
+            return;
+        }
+
+        let start = y
+            .saturating_mul(self.size.0)
+            .saturating_add(x)
+            .saturating_mul(PIXEL_SIZE);
+        let color = [color.r, color.g, color.b, color.a];
+
+        if let Some(pixel) = self.pixels.get_mut(start..start.saturating_add(PIXEL_SIZE)) {
+            if *pixel == color {
+                return;
+            }
+
+            pixel.copy_from_slice(&color);
+            self.dirty = Some(self.dirty.map_or(
+                ((x, y), (x, y)),
+                |((min_x, min_y), (max_x, max_y))| {
+                    ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+                },
+            ));
+        }
+    }
+
+    /// Uploads the pixels that changed to the GPU, and draws the texture
+    /// stretched over `dest`.
+    ///
+    /// Returns the number of bytes uploaded.
+    #[allow(clippy::as_conversions)]
+    pub fn draw(&mut self, drawer: &mut impl RaylibDraw, dest: Rectangle) -> usize {
+        let Some(texture) = &mut self.texture else {
+            return 0;
+        };
+        let uploaded = match self.dirty.take() {
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                let width = max_x.saturating_sub(min_x).saturating_add(1);
+                let height = max_y.saturating_sub(min_y).saturating_add(1);
+                let changed = width.saturating_mul(height);
+
+                if changed as f32
+                    > self.size.0.saturating_mul(self.size.1) as f32 * self.partial_upload_threshold
+                {
+                    texture.update_texture(&self.pixels);
+                    self.pixels.len()
+                } else {
+                    let row_length = self.size.0.saturating_mul(PIXEL_SIZE);
+
+                    self.changed.clear();
+                    (min_y..=max_y).for_each(|y| {
+                        let start = y
+                            .saturating_mul(row_length)
+                            .saturating_add(min_x.saturating_mul(PIXEL_SIZE));
+
+                        self.changed.extend_from_slice(
+                            self.pixels
+                                .get(start..start.saturating_add(width.saturating_mul(PIXEL_SIZE)))
+                                .unwrap_or_default(),
+                        );
+                    });
+                    texture.update_texture_rec(
+                        Rectangle::new(min_x as f32, min_y as f32, width as f32, height as f32),
+                        &self.changed,
+                    );
+                    self.changed.len()
+                }
+            }
+            None => 0,
+        };
+
+        drawer.draw_texture_pro(
+            &*texture,
+            Rectangle::new(0., 0., self.size.0 as f32, self.size.1 as f32),
+            dest,
+            Vector2::zero(),
+            0.,
+            Color::WHITE,
+        );
+
+        uploaded
+    }
+}
+
+/// Clamps a share of the texture to `0.0..=1.0`, replacing NaN with
+/// [`DEFAULT_PARTIAL_UPLOAD_THRESHOLD`].
+const fn valid_threshold(threshold: f32) -> f32 {
+    if threshold.is_nan() {
+        DEFAULT_PARTIAL_UPLOAD_THRESHOLD
+    } else {
+        threshold.clamp(0., 1.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_validation() {
+        assert!((valid_threshold(0.25) - 0.25).abs() < f32::EPSILON);
+        assert!(valid_threshold(-1.).abs() < f32::EPSILON);
+        assert!((valid_threshold(2.) - 1.).abs() < f32::EPSILON);
+        assert!((valid_threshold(f32::INFINITY) - 1.).abs() < f32::EPSILON);
+        assert!(
+            (valid_threshold(f32::NAN) - DEFAULT_PARTIAL_UPLOAD_THRESHOLD).abs() < f32::EPSILON
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Writing of rendered frames to numbered image files, to be put
+//! together into a video.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender},
+    thread::{self, JoinHandle},
+};
+
+use raylib::texture::Image;
+
+/// The number of frames that may wait to be written before queueing
+/// another one blocks.
+const QUEUE_LENGTH: usize = 8;
+
+/// Checks whether another multiple of `every` generations was passed
+/// since the `last` one a frame was written at.
+pub(crate) fn is_due(last: Option<u64>, every: u64, generation: u64) -> bool {
+    last.is_none_or(|last| last.checked_div(every) != generation.checked_div(every))
+}
+
+/// Writes an image to a file, in the format given by its extension.
+pub(crate) fn write_image(image: &Image, path: &Path) -> Result<(), String> {
+    if path.to_str().is_some_and(|file| image.export_image(file)) {
+        Ok(())
+    } else {
+        Err(format!("couldn't write {}", path.display()))
+    }
+}
+
+/// A rendered frame along with the file to write it to.
+struct Frame(PathBuf, Image);
+
+// SAFETY: The image owns its pixels, and writing them out doesn't
+// involve the GPU, so it may be done on another thread.
+unsafe impl Send for Frame {}
+
+/// Writes frames on a background thread, so that encoding them doesn't
+/// hold up the window.
+pub(crate) struct FrameExport {
+    dir: PathBuf,
+    /// Write a frame every this many generations.
+    every: u64,
+    /// The generation of the last frame queued.
+    last: Option<u64>,
+    count: usize,
+    sender: Option<SyncSender<Frame>>,
+    errors: Receiver<String>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl FrameExport {
+    /// Creates the directory (if needed) and starts the writing thread.
+    pub fn start(dir: &Path, every: u64) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        let (sender, frames) = mpsc::sync_channel::<Frame>(QUEUE_LENGTH);
+        let (error_sender, errors) = mpsc::channel();
+        let worker = thread::Builder::new()
+            .name("frame-export".to_owned())
+            .spawn(move || {
+                for Frame(path, image) in frames {
+                    if let Err(error) = write_image(&image, &path) {
+                        let _ = error_sender.send(error);
+                        return;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            dir: dir.to_owned(),
+            every: every.max(1),
+            last: None,
+            count: 0,
+            sender: Some(sender),
+            errors,
+            worker: Some(worker),
+        })
+    }
+
+    /// Checks whether a frame should be written at the given
+    /// generation (see [`is_due()`]).
+    pub fn is_due(&self, generation: u64) -> bool {
+        is_due(self.last, self.every, generation)
+    }
+
+    /// Queues the frame rendered at the given generation, blocking if
+    /// too many are waiting to be written already.
+    ///
+    /// Fails if an earlier frame couldn't be written.
+    pub fn push(&mut self, generation: u64, image: Image) -> Result<(), String> {
+        self.error()?;
+        self.last = Some(generation);
+        self.count = self.count.saturating_add(1);
+
+        let path = self.dir.join(format!("frame_{:06}.png", self.count));
+        let sent = self
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.send(Frame(path, image)).is_ok());
+
+        if sent {
+            Ok(())
+        } else {
+            Err(self
+                .errors
+                .recv()
+                .unwrap_or_else(|_| "the writing thread stopped".to_owned()))
+        }
+    }
+
+    /// Fails if a frame couldn't be written.
+    pub fn error(&self) -> Result<(), String> {
+        self.errors.try_recv().map_or(Ok(()), Err)
+    }
+
+    /// Waits for the queued frames to be written, returning how many
+    /// were queued in total.
+    pub fn finish(mut self) -> Result<usize, String> {
+        self.join();
+        self.error().map(|()| self.count)
+    }
+
+    /// Closes the queue and waits for the writing thread to empty it.
+    fn join(&mut self) {
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for FrameExport {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
@@ -0,0 +1,184 @@
+//! Saving and loading of the state of a frontend as JSON, to resume a
+//! long run after a restart (see e.g.
+//! [`generic::RaylibFrontend::save_state()`](crate::generic::RaylibFrontend::save_state())).
+
+use std::{error::Error, fmt, fs, io, path::Path, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The version of the format written, increased whenever it changes.
+///
+/// Files written by newer versions are refused.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Everything saved about a frontend, with the cells stored as `C`.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SessionState<C> {
+    pub version: u32,
+    pub generation: u64,
+    pub update_rate: Duration,
+    pub paused: bool,
+    pub grid_size: (usize, usize),
+    pub view: Option<View>,
+    pub cells: C,
+}
+
+/// The part of the grid being looked at.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct View {
+    pub zoom: f32,
+    pub offset: (f32, f32),
+}
+
+/// Just the version of a saved state, read before the rest of it.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+/// An error while saving or loading a state.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SessionError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file isn't a valid saved state.
+    Format(serde_json::Error),
+    /// The state was saved in a newer version of the format.
+    UnsupportedVersion(u32),
+    /// The state doesn't fit the frontend (e.g. its grid size).
+    Mismatch(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "couldn't access the saved state: {error}"),
+            Self::Format(error) => write!(f, "invalid saved state: {error}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "the state was saved in version {version} of the format, \
+                 only versions up to {FORMAT_VERSION} are supported"
+            ),
+            Self::Mismatch(reason) => write!(f, "the saved state doesn't fit: {reason}"),
+        }
+    }
+}
+
+impl Error for SessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Format(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SessionError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Format(error)
+    }
+}
+
+/// Writes a state to a file.
+pub(crate) fn save<C: Serialize>(path: &Path, state: &SessionState<C>) -> Result<(), SessionError> {
+    fs::write(path, serde_json::to_vec(state)?)?;
+
+    Ok(())
+}
+
+/// Reads a state from a file, checking its version first.
+pub(crate) fn load<C: DeserializeOwned>(path: &Path) -> Result<SessionState<C>, SessionError> {
+    let text = fs::read_to_string(path)?;
+    let Header { version } = serde_json::from_str(&text)?;
+
+    if version > FORMAT_VERSION {
+        return Err(SessionError::UnsupportedVersion(version));
+    }
+
+    Ok(serde_json::from_str(&text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+
+    /// Returns a path in the temporary directory, unique to the test.
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("lifers-raylib-session-{name}.json"))
+    }
+
+    fn state(version: u32) -> SessionState<Vec<((usize, usize), u8)>> {
+        SessionState {
+            version,
+            generation: 42,
+            update_rate: Duration::from_millis(30),
+            paused: true,
+            grid_size: (10, 8),
+            view: Some(View {
+                zoom: 2.,
+                offset: (1.5, -3.),
+            }),
+            cells: vec![((1, 2), 3), ((9, 7), 1)],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_path("round-trip");
+
+        save(&path, &state(FORMAT_VERSION)).unwrap();
+        let loaded: SessionState<Vec<((usize, usize), u8)>> = load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.version, FORMAT_VERSION);
+        assert_eq!(loaded.generation, 42);
+        assert_eq!(loaded.update_rate, Duration::from_millis(30));
+        assert!(loaded.paused);
+        assert_eq!(loaded.grid_size, (10, 8));
+        assert!(
+            loaded.view.is_some_and(
+                |view| view.zoom.to_bits() == 2f32.to_bits() && view.offset == (1.5, -3.)
+            )
+        );
+        assert_eq!(loaded.cells, [((1, 2), 3), ((9, 7), 1)]);
+    }
+
+    #[test]
+    fn newer_version() {
+        let path = temp_path("newer-version");
+        let newer = FORMAT_VERSION.saturating_add(1);
+
+        save(&path, &state(newer)).unwrap();
+        let loaded = load::<Vec<((usize, usize), u8)>>(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(
+            matches!(loaded, Err(SessionError::UnsupportedVersion(version)) if version == newer)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let path = temp_path("invalid");
+
+        fs::write(&path, "{\"cells\": []}").unwrap();
+        let loaded = load::<Vec<((usize, usize), u8)>>(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(loaded, Err(SessionError::Format(_))));
+        assert!(matches!(
+            load::<Vec<u8>>(&temp_path("missing")),
+            Err(SessionError::Io(_))
+        ));
+    }
+}
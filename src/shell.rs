@@ -0,0 +1,218 @@
+//! The state both frontends share, which doesn't depend on the kind of
+//! automaton: the HUD, the toasts and the window title, stepping on a
+//! background thread and in turbo mode, pausing while the window is
+//! unfocused or minimized, the history, the clipboard, the pattern
+//! library, the statistics log and autosaving.
+
+use std::{borrow::Cow, io, path::Path, time::Duration};
+
+#[cfg(feature = "serde")]
+use crate::autosave::Autosave;
+use crate::{
+    clipboard::{Clipboard, SystemClipboard},
+    history::History,
+    hud::{
+        format_title, Hud, HudCallback, RateMeter, Toasts, DEFAULT_TITLE, DEFAULT_TOAST_DURATION,
+    },
+    library::PatternLibrary,
+    patterns::Pattern,
+    stats::StatsLog,
+    stepper::{Step, Stepper},
+    timer::DEFAULT_TURBO_BUDGET,
+};
+use lifers::engine::ExecutionState;
+
+/// The state shared by the frontends, for an automaton of type `A`.
+///
+/// `F` is the frontend itself, which the hooks run on.
+pub(crate) struct Shell<A, F> {
+    pub hud: Hud,
+    pub rate_meter: RateMeter,
+    pub hud_callback: Option<HudCallback>,
+    pub toasts: Toasts,
+    pub title: Cow<'static, str>,
+    /// The title last given to the window, with the statistics.
+    pub shown_title: String,
+    pub title_format: Option<Cow<'static, str>>,
+    pub turbo: bool,
+    pub turbo_budget: Duration,
+    pub paused_before_turbo: bool,
+    pub pause_on_focus_loss: bool,
+    /// Whether the simulation was paused by the window losing focus.
+    pub focus_paused: bool,
+    pub simulate_when_minimized: bool,
+    /// Whether the simulation was paused by the window being minimized.
+    pub minimize_paused: bool,
+    pub was_minimized: bool,
+    pub stepper: Option<Stepper<A, ExecutionState>>,
+    /// The generations received from the stepper, reused across frames.
+    pub received_steps: Vec<Step<A, ExecutionState>>,
+    pub history: Option<History<A>>,
+    pub clipboard: Box<dyn Clipboard>,
+    pub pending_paste: Option<Pattern>,
+    pub library: Option<PatternLibrary>,
+    /// The selected entry of the pattern library, if its list is shown.
+    pub library_selected: Option<usize>,
+    pub stats_log: Option<StatsLog>,
+    pub stats_metrics: Vec<(String, Box<dyn Fn(&A) -> f32>)>,
+    /// The quick save key actions, run with the default key actions once
+    /// enabled, as they need the automaton to be [`Clone`].
+    pub quick_save_hook: Option<fn(&mut F)>,
+    /// The session key actions, run with the default key actions once
+    /// enabled, as they need the cells to be serializable.
+    #[cfg(feature = "serde")]
+    pub session_hook: Option<fn(&mut F)>,
+    #[cfg(feature = "serde")]
+    pub autosave: Option<Autosave<F>>,
+    pub last_autosave: Option<u64>,
+}
+
+impl<A, F> Shell<A, F> {
+    /// Creates the state of a frontend that was just opened.
+    pub fn new() -> Self {
+        Self {
+            hud: Hud::new(),
+            rate_meter: RateMeter::new(),
+            hud_callback: None,
+            toasts: Toasts::new(),
+            title: Cow::Borrowed(DEFAULT_TITLE),
+            shown_title: DEFAULT_TITLE.to_owned(),
+            title_format: None,
+            turbo: false,
+            turbo_budget: DEFAULT_TURBO_BUDGET,
+            paused_before_turbo: false,
+            pause_on_focus_loss: false,
+            focus_paused: false,
+            simulate_when_minimized: true,
+            minimize_paused: false,
+            was_minimized: false,
+            stepper: None,
+            received_steps: Vec::new(),
+            history: None,
+            clipboard: Box::new(SystemClipboard),
+            pending_paste: None,
+            library: None,
+            library_selected: None,
+            stats_log: None,
+            stats_metrics: Vec::new(),
+            quick_save_hook: None,
+            #[cfg(feature = "serde")]
+            session_hook: None,
+            #[cfg(feature = "serde")]
+            autosave: None,
+            last_autosave: None,
+        }
+    }
+
+    /// Shows a short message for a couple of seconds.
+    pub fn toast(&mut self, text: impl Into<Cow<'static, str>>) {
+        self.toasts.push(text.into(), DEFAULT_TOAST_DURATION);
+    }
+
+    /// Builds the window title along with the statistics, if enabled,
+    /// returning it if it differs from the one shown.
+    ///
+    /// Null characters are left out, as the window can't show them.
+    pub fn update_title(&mut self, generation: u64, population: Option<usize>) -> Option<&str> {
+        let title = self
+            .title_format
+            .as_ref()
+            .map_or_else(
+                || self.title.to_string(),
+                |format| {
+                    format_title(
+                        &self.title,
+                        format,
+                        generation,
+                        population,
+                        self.rate_meter.rate(),
+                    )
+                },
+            )
+            .replace('\0', "");
+
+        if title == self.shown_title {
+            return None;
+        }
+
+        self.shown_title = title;
+
+        Some(self.shown_title.as_str())
+    }
+
+    /// Tells whether the simulation has to be paused (`Some(true)`) or
+    /// resumed (`Some(false)`) as the window loses or regains focus,
+    /// given whether it's paused at the moment.
+    pub fn check_focus(&mut self, focused: bool, paused: bool) -> Option<bool> {
+        if !self.pause_on_focus_loss {
+            return None;
+        }
+
+        pause_while(&mut self.focus_paused, !focused, paused)
+    }
+
+    /// Like [`check_focus()`](Self::check_focus()), but for the window
+    /// being minimized or hidden.
+    pub fn check_minimized(&mut self, minimized: bool, paused: bool) -> Option<bool> {
+        if self.simulate_when_minimized {
+            return None;
+        }
+
+        pause_while(&mut self.minimize_paused, minimized, paused)
+    }
+
+    /// Drops the generations being computed on the background thread,
+    /// if any.
+    pub fn cancel_steps(&mut self) {
+        if let Some(stepper) = &mut self.stepper {
+            stepper.cancel();
+        }
+    }
+
+    /// Starts writing the statistics to a file, with a column for each
+    /// of the metrics, stopping the previous log first.
+    ///
+    /// Fails if the file can't be opened.
+    pub fn start_stats_log(&mut self, path: &Path, append: bool) -> io::Result<()> {
+        self.stop_stats_log();
+
+        let names: Vec<_> = self
+            .stats_metrics
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        self.stats_log = Some(StatsLog::start(path, append, &names)?);
+
+        Ok(())
+    }
+
+    /// Stops logging the statistics, writing out the remaining rows.
+    pub fn stop_stats_log(&mut self) {
+        let Some(log) = self.stats_log.take() else {
+            return;
+        };
+
+        if let Err(error) = log.finish() {
+            self.toast(format!("Statistics log failed: {error}"));
+        }
+    }
+}
+
+/// Tells whether the simulation has to be paused (`Some(true)`) or
+/// resumed (`Some(false)`) while a condition holds, unless it was
+/// already paused when it started to, with `paused_by_it` remembering
+/// whether it was paused because of it.
+fn pause_while(paused_by_it: &mut bool, condition: bool, paused: bool) -> Option<bool> {
+    if condition && !*paused_by_it && !paused {
+        *paused_by_it = true;
+
+        Some(true)
+    } else if !condition && *paused_by_it {
+        *paused_by_it = false;
+
+        // NOTE: The user might have unpaused it in the meantime
+        paused.then_some(false)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,150 @@
+//! Rendering of the grid only every few generations.
+
+use raylib::color::Color;
+
+/// The colors of the cells as they were last rendered, reused until
+/// enough generations pass.
+///
+/// The region is described by a key (e.g. the range of visible cells),
+/// a snapshot of a different region is never reused.
+pub struct GridSnapshot {
+    /// Render every this many generations.
+    pub every: usize,
+    rendered: Option<(u64, (bool, [usize; 4]))>,
+    colors: Vec<Color>,
+    /// Counts the invalidations, e.g. to notice the cells changing
+    /// without a new generation.
+    invalidations: u64,
+}
+
+impl GridSnapshot {
+    /// Creates a snapshot that is refreshed every generation.
+    pub const fn new() -> Self {
+        Self {
+            every: 1,
+            rendered: None,
+            colors: Vec::new(),
+            invalidations: 0,
+        }
+    }
+
+    /// Checks whether the grid should be rendered anew at the given
+    /// generation, clearing the snapshot if so.
+    ///
+    /// If not, the colors should be taken from the snapshot instead.
+    pub fn begin(&mut self, generation: u64, key: (bool, [usize; 4])) -> bool {
+        let every = u64::try_from(self.every).unwrap_or(u64::MAX);
+        let fresh = self.rendered.is_some_and(|(rendered, rendered_key)| {
+            rendered_key == key
+                && generation
+                    .checked_sub(rendered)
+                    .is_some_and(|passed| passed < every)
+        });
+
+        if fresh {
+            return false;
+        }
+
+        self.colors.clear();
+        self.rendered = (self.every > 1).then_some((generation, key));
+
+        true
+    }
+
+    /// Records the color of the next cell, if the snapshot is kept at
+    /// all.
+    pub fn record(&mut self, color: Color) {
+        if self.every > 1 {
+            self.colors.push(color);
+        }
+    }
+
+    /// Returns the recorded color of the cell at the given index.
+    pub fn color(&self, index: usize) -> Color {
+        self.colors.get(index).copied().unwrap_or(Color::BLANK)
+    }
+
+    /// Makes the next [`begin()`](Self::begin()) render the grid anew.
+    pub fn invalidate(&mut self) {
+        self.rendered = None;
+        self.invalidations = self.invalidations.wrapping_add(1);
+    }
+
+    /// Returns the number of times the snapshot was invalidated.
+    pub const fn invalidations(&self) -> u64 {
+        self.invalidations
+    }
+}
+
+/// The colors of all the cells of a sparse grid, rendered once per
+/// generation, so that they aren't looked up every frame.
+pub struct DenseColors {
+    size: (usize, usize),
+    /// The generation and the number of invalidations of the
+    /// [`GridSnapshot`] the colors were rendered at.
+    rendered: Option<(u64, u64)>,
+    /// The colors of the cells, or [`None`] where there are none.
+    colors: Vec<Option<Color>>,
+}
+
+impl DenseColors {
+    /// Creates an empty buffer, rendered on the first
+    /// [`fill()`](Self::fill()).
+    pub const fn new() -> Self {
+        Self {
+            size: (0, 0),
+            rendered: None,
+            colors: Vec::new(),
+        }
+    }
+
+    /// Checks whether the colors were rendered with the given key and
+    /// grid size.
+    pub fn is_fresh(&self, key: (u64, u64), size: (usize, usize)) -> bool {
+        self.rendered == Some(key) && self.size == size
+    }
+
+    /// Renders the given cells of a grid of the given size, leaving out
+    /// the ones outside of it.
+    ///
+    /// The buffer is only reallocated if the size changed.
+    pub fn fill(
+        &mut self,
+        key: (u64, u64),
+        (width, height): (usize, usize),
+        cells: impl IntoIterator<Item = ((usize, usize), Color)>,
+    ) {
+        if self.size == (width, height) {
+            self.colors.fill(None);
+        } else {
+            self.size = (width, height);
+            self.colors.clear();
+            self.colors.resize(width.saturating_mul(height), None);
+        }
+
+        cells.into_iter().for_each(|((x, y), color)| {
+            if x < width && y < height {
+                if let Some(slot) = self
+                    .colors
+                    .get_mut(y.saturating_mul(width).saturating_add(x))
+                {
+                    *slot = Some(color);
+                }
+            }
+        });
+        self.rendered = Some(key);
+    }
+
+    /// Returns the color of the cell at the given position, or [`None`]
+    /// if there's none.
+    pub fn color_at(&self, (x, y): (usize, usize)) -> Option<Color> {
+        if x >= self.size.0 {
+            return None;
+        }
+
+        self.colors
+            .get(y.saturating_mul(self.size.0).saturating_add(x))
+            .copied()
+            .flatten()
+    }
+}
@@ -0,0 +1,61 @@
+//! Detection of automata that stopped changing or started repeating.
+
+use std::collections::VecDeque;
+
+/// Remembers the last few states (along with their hashes) to notice
+/// when one of them comes up again.
+///
+/// The states are only compared if their hashes match, so collisions
+/// aren't mistaken for cycles.  At most `max_period` of them are kept.
+pub(crate) struct CycleDetector<T> {
+    states: VecDeque<(u64, T)>,
+    max_period: usize,
+    /// Whether the last state was a repeat, so that a cycle is only
+    /// reported once.
+    in_cycle: bool,
+}
+
+impl<T: Eq> CycleDetector<T> {
+    /// Creates a detector of cycles up to the given length (`1` only
+    /// detects states that don't change).
+    pub fn new(max_period: usize) -> Self {
+        let max_period = max_period.max(1);
+
+        Self {
+            states: VecDeque::with_capacity(max_period),
+            max_period,
+            in_cycle: false,
+        }
+    }
+
+    /// Checks whether no states were recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Records a new state, returning the period if it matches one of
+    /// the remembered ones and the previous state didn't.
+    pub fn push(&mut self, hash: u64, state: T) -> Option<usize> {
+        let period = self
+            .states
+            .iter()
+            .rev()
+            .position(|(previous_hash, previous)| *previous_hash == hash && *previous == state)
+            .map(|index| index.saturating_add(1));
+        let entered = period.is_some() && !self.in_cycle;
+
+        self.in_cycle = period.is_some();
+        if self.states.len() >= self.max_period {
+            self.states.pop_front();
+        }
+        self.states.push_back((hash, state));
+
+        period.filter(|_| entered)
+    }
+
+    /// Forgets all the recorded states.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.in_cycle = false;
+    }
+}
@@ -0,0 +1,107 @@
+//! Logging of per-generation statistics to a CSV file.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::hud::StepStats;
+
+/// The columns written before the custom metrics.
+const HEADER: &str = "generation,population,births,deaths";
+/// The log is flushed every this many rows, so that a crash loses at
+/// most this many.
+const FLUSH_EVERY: usize = 64;
+
+/// A CSV file that gets a row per generation.
+///
+/// Missing statistics (e.g. births when they aren't tracked) are left
+/// empty.
+pub(crate) struct StatsLog {
+    writer: BufWriter<File>,
+    /// The number of custom metrics named in the header.
+    metrics: usize,
+    unflushed: usize,
+}
+
+impl StatsLog {
+    /// Opens the log, either appending to the file or truncating it,
+    /// and writes the header if the file is empty.
+    pub fn start(path: &Path, append: bool, metric_names: &[&str]) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+        let mut log = Self {
+            writer: BufWriter::new(file),
+            metrics: metric_names.len(),
+            unflushed: 0,
+        };
+
+        if is_empty {
+            write!(log.writer, "{HEADER}")?;
+            for name in metric_names {
+                write!(log.writer, ",{}", escape(name))?;
+            }
+            writeln!(log.writer)?;
+            log.writer.flush()?;
+        }
+
+        Ok(log)
+    }
+
+    /// Returns the number of custom metrics logged.
+    pub const fn metrics(&self) -> usize {
+        self.metrics
+    }
+
+    /// Writes the row of a generation, flushing the log every few
+    /// rows.
+    pub fn write_row(
+        &mut self,
+        generation: u64,
+        population: Option<usize>,
+        step_stats: Option<StepStats>,
+        metrics: impl IntoIterator<Item = f32>,
+    ) -> io::Result<()> {
+        let optional = |value: Option<usize>| value.map(|value| value.to_string());
+
+        write!(
+            self.writer,
+            "{generation},{},{},{}",
+            optional(population).unwrap_or_default(),
+            optional(step_stats.map(|stats| stats.births)).unwrap_or_default(),
+            optional(step_stats.map(|stats| stats.deaths)).unwrap_or_default(),
+        )?;
+        for value in metrics.into_iter().take(self.metrics) {
+            write!(self.writer, ",{value}")?;
+        }
+        writeln!(self.writer)?;
+
+        self.unflushed = self.unflushed.saturating_add(1);
+        if self.unflushed >= FLUSH_EVERY {
+            self.unflushed = 0;
+            self.writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes out the remaining rows.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Quotes a column name if it contains special characters.
+fn escape(name: &str) -> String {
+    if name.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_owned()
+    }
+}
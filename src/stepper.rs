@@ -0,0 +1,166 @@
+//! Computing of generations on a background thread, so that slow rules
+//! don't hold up the window.
+
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::clock::Instant;
+
+/// A state to compute a number of steps from.
+struct Batch<T> {
+    epoch: u64,
+    state: T,
+    steps: usize,
+}
+
+/// A generation computed by the worker.
+pub(crate) struct Step<T, R> {
+    epoch: u64,
+    /// The state after the step.
+    pub state: T,
+    /// What the step returned.
+    pub result: R,
+    /// How long the step took.
+    pub duration: Duration,
+}
+
+/// Steps copies of a state on a background thread, sending each
+/// generation back as soon as it's computed.
+///
+/// Only one batch is computed at a time, and cancelling it makes the
+/// worker drop it after the current step, so the generations received
+/// afterwards are always computed from the latest state sent.
+pub(crate) struct Stepper<T, R> {
+    batches: Option<Sender<Batch<T>>>,
+    steps: Receiver<Step<T, R>>,
+    /// Bumped on every cancellation, batches of earlier ones are
+    /// dropped.
+    epoch: Arc<AtomicU64>,
+    /// The number of steps requested but not received yet.
+    in_flight: usize,
+    clone: fn(&T) -> T,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<T, R> Stepper<T, R>
+where
+    T: Clone + Send + 'static,
+    R: Send + 'static,
+{
+    /// Starts the worker thread, which computes steps with `step`.
+    pub fn start(step: fn(&mut T) -> R) -> io::Result<Self> {
+        let (batches, batch_receiver) = mpsc::channel::<Batch<T>>();
+        let (step_sender, steps) = mpsc::channel();
+        let epoch = Arc::new(AtomicU64::new(0));
+        let current = Arc::clone(&epoch);
+        let worker = thread::Builder::new()
+            .name("stepper".to_owned())
+            .spawn(move || {
+                for Batch {
+                    epoch,
+                    mut state,
+                    steps,
+                } in batch_receiver
+                {
+                    for _ in 0..steps {
+                        if current.load(Ordering::Relaxed) != epoch {
+                            break;
+                        }
+
+                        let start = Instant::now();
+                        let result = step(&mut state);
+                        let step = Step {
+                            epoch,
+                            state: state.clone(),
+                            result,
+                            duration: start.elapsed(),
+                        };
+
+                        if step_sender.send(step).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            batches: Some(batches),
+            steps,
+            epoch,
+            in_flight: 0,
+            clone: T::clone,
+            worker: Some(worker),
+        })
+    }
+}
+
+impl<T, R> Stepper<T, R> {
+    /// Checks whether requested steps are still being computed.
+    pub const fn is_busy(&self) -> bool {
+        self.in_flight > 0
+    }
+
+    /// Makes the worker compute `steps` generations from a copy of
+    /// `state`, unless it's still busy with the previous batch.
+    ///
+    /// Returns whether the batch was sent.
+    pub fn request(&mut self, state: &T, steps: usize) -> bool {
+        if self.is_busy() || steps == 0 {
+            return false;
+        }
+
+        let batch = Batch {
+            epoch: self.epoch.load(Ordering::Relaxed),
+            state: (self.clone)(state),
+            steps,
+        };
+        let sent = self
+            .batches
+            .as_ref()
+            .is_some_and(|batches| batches.send(batch).is_ok());
+
+        if sent {
+            self.in_flight = steps;
+        }
+
+        sent
+    }
+
+    /// Replaces the contents of `steps` with the generations computed
+    /// since the last call, in order.
+    pub fn receive(&mut self, steps: &mut Vec<Step<T, R>>) {
+        let epoch = self.epoch.load(Ordering::Relaxed);
+
+        steps.clear();
+        steps.extend(self.steps.try_iter().filter(|step| step.epoch == epoch));
+
+        self.in_flight = self.in_flight.saturating_sub(steps.len());
+    }
+
+    /// Drops the batch being computed along with the generations not
+    /// received yet.
+    pub fn cancel(&mut self) {
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+        self.in_flight = 0;
+    }
+}
+
+impl<T, R> Drop for Stepper<T, R> {
+    fn drop(&mut self) {
+        self.cancel();
+        // NOTE: The worker stops once the queue is closed, after the step
+        // it's computing, if any
+        self.batches = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -1,19 +1,112 @@
-use std::time::{Duration, Instant};
+//! A timer for running things at a fixed rate in a frame loop.
+//!
+//! The frontends use it to compute generations, but it can be used on
+//! its own, e.g. for effects running at a rate of their own:
+//!
+//! ```ignore
+//! # use std::time::Duration;
+//! # use lifers_raylib::timer::{RepeatingTimer, TimerState, DEFAULT_MAX_CYCLES};
+//! let mut timer = RepeatingTimer::new(Duration::from_millis(100));
+//!
+//! loop {
+//!     if let TimerState::Finished(cycles) = timer.update(DEFAULT_MAX_CYCLES) {
+//!         /* Do something `cycles` times */
+//!     }
+//! }
+//! ```
 
+use std::time::Duration;
+
+use crate::clock::Instant;
+
+/// Converts the number of cycles per second into the duration of a
+/// cycle.
+///
+/// Returns [`None`] for rates of `0` or less, meaning the timer should
+/// never finish, and [`Duration::ZERO`] for rates too high to be
+/// represented.
+pub fn period_from_frequency(frequency: f32) -> Option<Duration> {
+    if frequency.is_nan() || frequency <= 0. {
+        return None;
+    }
+
+    Some(Duration::try_from_secs_f64(1. / f64::from(frequency)).unwrap_or(Duration::ZERO))
+}
+
+/// Converts the duration of a cycle into the number of cycles per
+/// second.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+pub fn frequency_from_period(period: Duration) -> f32 {
+    if period.is_zero() {
+        f32::INFINITY
+    } else {
+        (1. / period.as_secs_f64()) as f32
+    }
+}
+
+/// The outcome of a [`RepeatingTimer`] update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TimerState {
-    Ongoing, // or whatever
-    Finished,
+    /// The current cycle hasn't finished yet.
+    Ongoing,
+    /// The given number of cycles have passed.
+    Finished(u32),
+    /// The timer is paused, so no time has passed for it.
     Paused,
 }
 
+/// The default number of cycles handled by a single update.
+pub const DEFAULT_MAX_CYCLES: u32 = 1000;
+
+/// The default longest time handled by a single update.
+pub const DEFAULT_MAX_ELAPSED: Duration = Duration::from_millis(250);
+
+/// The default time spent computing generations each frame in turbo
+/// mode.
+pub const DEFAULT_TURBO_BUDGET: Duration = Duration::from_millis(10);
+
+/// A gradual change of the amount of time each cycle takes.
+#[derive(Clone, Debug)]
+struct Ramp {
+    from: Duration,
+    to: Duration,
+    over: Duration,
+    elapsed: Duration,
+}
+
+impl Ramp {
+    /// Returns the amount after the given time, along with whether the
+    /// ramp is finished.
+    fn advance(&mut self, elapsed: Duration) -> (Duration, bool) {
+        self.elapsed = self.elapsed.saturating_add(elapsed);
+
+        if self.elapsed >= self.over {
+            return (self.to, true);
+        }
+
+        let progress = self.elapsed.as_secs_f64() / self.over.as_secs_f64();
+        let amount = (self.to.as_secs_f64() - self.from.as_secs_f64())
+            .mul_add(progress, self.from.as_secs_f64());
+
+        (
+            Duration::try_from_secs_f64(amount).unwrap_or(self.to),
+            false,
+        )
+    }
+}
+
 /// Repeating timer
 ///
-/// Uses [`std::time::Instant`], so it is monotonic.
+/// Uses [`Instant`] (see [`clock`](crate::clock)), so it is monotonic.
+#[derive(Clone, Debug)]
 pub struct RepeatingTimer {
     amount: Duration,
     time_left: Duration,
     last_checked: Instant,
+    max_elapsed: Duration,
     paused: bool,
+    ramp: Option<Ramp>,
 }
 
 impl RepeatingTimer {
@@ -23,50 +116,117 @@ impl RepeatingTimer {
             amount,
             time_left: amount,
             last_checked: Instant::now(),
+            max_elapsed: DEFAULT_MAX_ELAPSED,
             paused: false,
+            ramp: None,
+        }
+    }
+
+    /// Creates a timer as if it was last updated at the given time.
+    #[cfg(test)]
+    fn with_last_checked(amount: Duration, last_checked: Instant) -> Self {
+        Self {
+            last_checked,
+            ..Self::new(amount)
+        }
+    }
+
+    /// Creates a new paused timer (see [`resume()`](Self::resume())).
+    pub fn new_paused(amount: Duration) -> Self {
+        Self {
+            paused: true,
+            ..Self::new(amount)
         }
     }
 
-    /// Updates the timer with the current time, returning whether the given
-    /// amount has passed or not.
-    pub fn update(&mut self) -> TimerState {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_checked);
+    /// Restarts the current cycle, as if the timer was just created.
+    ///
+    /// The amount of time each cycle takes and pausing are kept, but an
+    /// ongoing ramp is cancelled.
+    pub fn reset(&mut self) {
+        self.time_left = self.amount;
+        self.last_checked = Instant::now();
+        self.ramp = None;
+    }
+
+    /// Updates the timer with the current time, returning how many
+    /// times the given amount has passed, but at most `max_cycles`.
+    ///
+    /// The cycles over the limit are dropped instead of being carried
+    /// over to the next update.
+    pub fn update(&mut self, max_cycles: u32) -> TimerState {
+        self.update_at(Instant::now(), max_cycles)
+    }
+
+    /// Like [`update()`](Self::update), but with the given current time.
+    ///
+    /// If `now` is earlier than the last update, no time is considered
+    /// to have passed, and the time of the last update is kept, so that
+    /// the time until the clock is back to it isn't counted twice.
+    pub fn update_at(&mut self, now: Instant, max_cycles: u32) -> TimerState {
+        self.checked_update_at(now, max_cycles)
+            .unwrap_or_else(|| self.update_elapsed(Duration::ZERO, max_cycles))
+    }
+
+    /// Like [`update()`](Self::update), but uses [`Instant::checked_duration_since()`].
+    ///
+    /// Returns [`None`] if the clock went backwards since the last
+    /// update, leaving the timer as it was.
+    pub fn checked_update(&mut self, max_cycles: u32) -> Option<TimerState> {
+        self.checked_update_at(Instant::now(), max_cycles)
+    }
+
+    /// Like [`checked_update()`](Self::checked_update), but with the
+    /// given current time.
+    pub fn checked_update_at(&mut self, now: Instant, max_cycles: u32) -> Option<TimerState> {
+        let elapsed = now.checked_duration_since(self.last_checked)?;
 
         self.last_checked = now;
 
+        Some(self.update_elapsed(elapsed, max_cycles))
+    }
+
+    fn update_elapsed(&mut self, elapsed: Duration, max_cycles: u32) -> TimerState {
         if self.paused {
             TimerState::Paused
         } else {
-            self.update_state(elapsed)
+            self.update_ramp(elapsed);
+            self.update_state(elapsed, max_cycles)
         }
     }
 
-    /// Like [`update()`](Self::update), but uses [`Instant::checked_duration_since()`].
-    #[allow(dead_code)]
-    pub fn checked_update(&mut self) -> Option<TimerState> {
-        let now = Instant::now();
-        let elapsed = now.checked_duration_since(self.last_checked);
+    fn update_state(&mut self, elapsed: Duration, max_cycles: u32) -> TimerState {
+        // NOTE: After a long hitch (e.g. the window being dragged) the
+        // timer resumes instead of catching up with all of it
+        let elapsed = elapsed.min(self.max_elapsed);
 
-        self.last_checked = now;
-
-        elapsed.map(|x| self.update_state(x))
-    }
+        if max_cycles == 0 {
+            return TimerState::Ongoing;
+        }
+        // NOTE: A cycle is finished on every update
+        if self.amount.is_zero() {
+            return TimerState::Finished(1);
+        }
 
-    fn update_state(&mut self, elapsed: Duration) -> TimerState {
         if elapsed >= self.time_left {
-            self.time_left = if elapsed > self.amount {
-                self.amount
-            } else {
-                // NOTE: `elapsed` is checked to be greater than or equal to
-                // `self.time_left`
-                #[allow(clippy::arithmetic_side_effects)]
-                self.amount
-                    .checked_sub(elapsed - self.time_left)
-                    .unwrap_or(self.amount)
-            };
-
-            TimerState::Finished
+            // NOTE: `elapsed` is checked to be greater than or equal to
+            // `self.time_left`
+            #[allow(clippy::arithmetic_side_effects)]
+            let overshoot = elapsed - self.time_left;
+            let amount = self.amount.as_nanos();
+            let cycles = overshoot
+                .as_nanos()
+                .checked_div(amount)
+                .unwrap_or(0)
+                .saturating_add(1);
+            let into_cycle = overshoot.as_nanos().checked_rem(amount).unwrap_or(0);
+
+            self.time_left = u64::try_from(into_cycle)
+                .ok()
+                .and_then(|into_cycle| self.amount.checked_sub(Duration::from_nanos(into_cycle)))
+                .unwrap_or(self.amount);
+
+            TimerState::Finished(u32::try_from(cycles).unwrap_or(u32::MAX).min(max_cycles))
         } else {
             // NOTE: `elapsed` is checked to be smaller than `self.time_left`
             #[allow(clippy::arithmetic_side_effects)]
@@ -86,8 +246,507 @@ impl RepeatingTimer {
         self.paused = !self.paused;
     }
 
+    /// Pauses or unpauses the timer (see
+    /// [`toggle_pause()`](Self::toggle_pause())).
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Pauses the timer (see [`toggle_pause()`](Self::toggle_pause())).
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpauses the timer (see [`toggle_pause()`](Self::toggle_pause())).
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Checks whether the timer is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Changes the amount of time that each cycle takes.
+    ///
+    /// Unlike creating a new timer, the progress of the current cycle
+    /// is kept: the time left is scaled to the new amount, so that the
+    /// same fraction of the cycle remains.  Pausing is kept as well.
+    ///
+    /// Cancels the ongoing ramp (see [`ramp_to()`](Self::ramp_to())).
+    pub fn set_rate(&mut self, amount: Duration) {
+        self.ramp = None;
+        self.rescale(amount);
+    }
+
+    /// Gradually changes the amount of time that each cycle takes to
+    /// `target` over the given time, as the timer is updated.
+    ///
+    /// The ramp doesn't advance while paused.
+    pub fn ramp_to(&mut self, target: Duration, over: Duration) {
+        if over.is_zero() {
+            self.set_rate(target);
+            return;
+        }
+
+        self.ramp = Some(Ramp {
+            from: self.amount,
+            to: target,
+            over,
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    /// Advances the ongoing ramp by the given time.
+    fn update_ramp(&mut self, elapsed: Duration) {
+        let Some(ramp) = &mut self.ramp else {
+            return;
+        };
+        let (amount, finished) = ramp.advance(elapsed);
+
+        if finished {
+            self.ramp = None;
+        }
+
+        self.rescale(amount);
+    }
+
+    /// Changes the amount of time that each cycle takes, keeping the
+    /// progress of the current cycle.
+    fn rescale(&mut self, amount: Duration) {
+        self.time_left = if self.amount.is_zero() {
+            amount
+        } else {
+            self.time_left
+                .mul_f64(amount.as_secs_f64() / self.amount.as_secs_f64())
+                .min(amount)
+        };
+        self.amount = amount;
+    }
+
+    /// Returns how far along the current cycle is, from `0` (just
+    /// started) to `1` (about to finish), as of the last update.
+    ///
+    /// It's frozen while paused and always `0` if cycles take no time.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub fn progress(&self) -> f32 {
+        if self.amount.is_zero() {
+            return 0.;
+        }
+
+        (1. - self.time_left.as_secs_f64() / self.amount.as_secs_f64()).clamp(0., 1.) as f32
+    }
+
+    /// Sets the longest time handled by a single update (250 ms by
+    /// default), any time over it is dropped.
+    pub fn set_max_elapsed(&mut self, max_elapsed: Duration) {
+        self.max_elapsed = max_elapsed;
+    }
+
+    /// Returns the time left until the current cycle finishes, as of
+    /// the last update.
+    pub const fn remaining(&self) -> Duration {
+        self.time_left
+    }
+
+    /// Returns the time left until the current cycle finishes, counting
+    /// from now rather than the last update, or [`None`] while paused.
+    pub fn until_next_cycle(&self) -> Option<Duration> {
+        (!self.paused).then(|| self.time_left.saturating_sub(self.last_checked.elapsed()))
+    }
+
     /// Returns the amount of time that each cycle takes.
-    pub fn rate(&self) -> Duration {
+    pub const fn rate(&self) -> Duration {
         self.amount
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_CYCLES: u32 = DEFAULT_MAX_CYCLES;
+
+    const fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// Returns the time the given number of milliseconds after `start`.
+    fn after(start: Instant, millis: u64) -> Instant {
+        start.checked_add(ms(millis)).unwrap()
+    }
+
+    #[test]
+    fn carries_over_overshoot() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        assert_eq!(
+            timer.update_at(after(start, 30), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(
+            timer.update_at(after(start, 90), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(
+            timer.update_at(after(start, 120), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(timer.remaining(), ms(80));
+    }
+
+    #[test]
+    fn no_drift() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+        let mut cycles = 0_u32;
+
+        // NOTE: Updates that don't line up with the cycles
+        for millis in (30..=3000).step_by(30) {
+            if let TimerState::Finished(n) = timer.update_at(after(start, millis), MAX_CYCLES) {
+                cycles = cycles.saturating_add(n);
+            }
+        }
+
+        assert_eq!(cycles, 30);
+        assert_eq!(timer.remaining(), ms(100));
+    }
+
+    #[test]
+    fn several_cycles_in_one_update() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        assert_eq!(
+            timer.update_at(after(start, 250), MAX_CYCLES),
+            TimerState::Finished(2)
+        );
+        assert_eq!(timer.remaining(), ms(50));
+        assert_eq!(
+            timer.update_at(after(start, 500), 1),
+            TimerState::Finished(1)
+        );
+    }
+
+    #[test]
+    fn slow_frame() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(10), start);
+
+        // NOTE: Only `DEFAULT_MAX_ELAPSED` of the 2 s gap counts
+        assert_eq!(
+            timer.update_at(after(start, 2000), MAX_CYCLES),
+            TimerState::Finished(25)
+        );
+        // NOTE: No burst catching up with the rest afterwards
+        assert_eq!(
+            timer.update_at(after(start, 2010), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(
+            timer.update_at(after(start, 2015), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+
+        timer.set_max_elapsed(ms(2000));
+        assert_eq!(
+            timer.update_at(after(start, 4015), MAX_CYCLES),
+            TimerState::Finished(200)
+        );
+        assert_eq!(
+            timer.update_at(after(start, 9015), 3),
+            TimerState::Finished(3)
+        );
+    }
+
+    #[test]
+    fn update_elapsed() {
+        let mut timer = RepeatingTimer::new(ms(100));
+
+        assert_eq!(
+            timer.update_elapsed(ms(40), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(timer.remaining(), ms(60));
+        assert_eq!(
+            timer.update_elapsed(ms(60), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(timer.remaining(), ms(100));
+        assert_eq!(
+            timer.update_elapsed(ms(230), MAX_CYCLES),
+            TimerState::Finished(2)
+        );
+        assert_eq!(timer.remaining(), ms(70));
+        assert_eq!(
+            timer.update_elapsed(Duration::ZERO, MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(timer.remaining(), ms(70));
+
+        // NOTE: Nothing advances without any cycles allowed
+        assert_eq!(timer.update_elapsed(ms(200), 0), TimerState::Ongoing);
+        assert_eq!(timer.remaining(), ms(70));
+
+        timer.pause();
+        assert_eq!(
+            timer.update_elapsed(ms(200), MAX_CYCLES),
+            TimerState::Paused
+        );
+        assert_eq!(timer.remaining(), ms(70));
+    }
+
+    #[test]
+    fn update_elapsed_zero_amount() {
+        let mut timer = RepeatingTimer::new(Duration::ZERO);
+
+        assert_eq!(
+            timer.update_elapsed(Duration::ZERO, MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(
+            timer.update_elapsed(ms(100), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+    }
+
+    #[test]
+    fn pause() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.pause();
+        assert!(timer.is_paused());
+        assert_eq!(
+            timer.update_at(after(start, 500), MAX_CYCLES),
+            TimerState::Paused
+        );
+        assert_eq!(timer.remaining(), ms(100));
+        assert_eq!(timer.until_next_cycle(), None);
+
+        // NOTE: The time spent paused doesn't count after resuming
+        timer.resume();
+        assert_eq!(
+            timer.update_at(after(start, 530), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(timer.remaining(), ms(70));
+
+        timer.toggle_pause();
+        assert!(timer.is_paused());
+        timer.set_paused(false);
+        assert!(!timer.is_paused());
+    }
+
+    #[test]
+    fn checked_update() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), after(start, 100));
+
+        assert_eq!(
+            timer.checked_update_at(after(start, 150), MAX_CYCLES),
+            Some(TimerState::Ongoing)
+        );
+        assert_eq!(timer.remaining(), ms(50));
+        assert_eq!(timer.checked_update_at(after(start, 120), MAX_CYCLES), None);
+        assert_eq!(timer.remaining(), ms(50));
+        // NOTE: Counted from the last update that succeeded
+        assert_eq!(
+            timer.checked_update_at(after(start, 200), MAX_CYCLES),
+            Some(TimerState::Finished(1))
+        );
+        assert_eq!(timer.remaining(), ms(100));
+    }
+
+    #[test]
+    fn reset() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.update_at(after(start, 60), MAX_CYCLES);
+        timer.ramp_to(ms(50), ms(1000));
+        timer.pause();
+
+        let before = Instant::now();
+        timer.reset();
+
+        assert_eq!(timer.remaining(), ms(100));
+        assert_eq!(timer.rate(), ms(100));
+        assert!(timer.ramp.is_none());
+        assert!(timer.is_paused());
+        assert!(timer.last_checked >= before);
+    }
+
+    #[test]
+    fn new_paused() {
+        let mut timer = RepeatingTimer::new_paused(ms(100));
+        let start = timer.last_checked;
+
+        assert!(timer.is_paused());
+        assert_eq!(
+            timer.update_at(after(start, 500), MAX_CYCLES),
+            TimerState::Paused
+        );
+        assert_eq!(timer.remaining(), ms(100));
+
+        timer.resume();
+        assert_eq!(
+            timer.update_at(after(start, 540), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(timer.remaining(), ms(60));
+    }
+
+    #[test]
+    fn rescale() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.update_at(after(start, 75), MAX_CYCLES);
+        timer.rescale(ms(400));
+        assert_eq!(timer.remaining(), ms(100));
+        timer.rescale(ms(40));
+        assert_eq!(timer.remaining(), ms(10));
+
+        // NOTE: A cycle taking no time has no progress to keep
+        timer.rescale(Duration::ZERO);
+        assert_eq!(timer.remaining(), Duration::ZERO);
+        timer.rescale(ms(30));
+        assert_eq!(timer.remaining(), ms(30));
+    }
+
+    #[test]
+    fn set_rate_keeps_progress() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.update_at(after(start, 25), MAX_CYCLES);
+        timer.set_rate(ms(200));
+        assert_eq!(timer.rate(), ms(200));
+        assert_eq!(timer.remaining(), ms(150));
+    }
+
+    #[test]
+    fn set_rate_below_remaining() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(1000), start);
+
+        timer.update_at(after(start, 100), MAX_CYCLES);
+        timer.set_rate(ms(10));
+        // NOTE: Not the 900 ms that were left of the old cycle
+        assert_eq!(timer.remaining(), ms(9));
+        assert_eq!(
+            timer.update_at(after(start, 109), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(timer.remaining(), ms(10));
+    }
+
+    #[test]
+    fn set_rate_while_paused() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.update_at(after(start, 50), MAX_CYCLES);
+        timer.pause();
+        timer.set_rate(ms(400));
+        assert!(timer.is_paused());
+        assert_eq!(timer.remaining(), ms(200));
+        assert_eq!(
+            timer.update_at(after(start, 1000), MAX_CYCLES),
+            TimerState::Paused
+        );
+        assert_eq!(timer.remaining(), ms(200));
+
+        timer.resume();
+        assert_eq!(
+            timer.update_at(after(start, 1200), MAX_CYCLES),
+            TimerState::Finished(1)
+        );
+        assert_eq!(timer.remaining(), ms(400));
+    }
+
+    #[test]
+    fn ramp_to() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        timer.ramp_to(ms(300), ms(1000));
+        assert_eq!(timer.rate(), ms(100));
+        timer.update_at(after(start, 250), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(150));
+        timer.update_at(after(start, 500), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(200));
+
+        // NOTE: The ramp doesn't advance while paused
+        timer.pause();
+        timer.update_at(after(start, 5000), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(200));
+        timer.resume();
+
+        timer.update_at(after(start, 5250), MAX_CYCLES);
+        timer.update_at(after(start, 5500), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(300));
+        assert!(timer.ramp.is_none());
+        timer.update_at(after(start, 5750), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(300));
+    }
+
+    #[test]
+    fn ramp_to_instantly() {
+        let mut timer = RepeatingTimer::new(ms(100));
+
+        timer.ramp_to(ms(50), Duration::ZERO);
+        assert_eq!(timer.rate(), ms(50));
+        assert!(timer.ramp.is_none());
+
+        // NOTE: Setting the rate cancels the ramp
+        timer.ramp_to(ms(300), ms(1000));
+        timer.set_rate(ms(20));
+        timer.update_elapsed(ms(100), MAX_CYCLES);
+        assert_eq!(timer.rate(), ms(20));
+    }
+
+    // NOTE: The fractions are exact
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn progress() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), start);
+
+        assert_eq!(timer.progress(), 0.);
+        timer.update_at(after(start, 25), MAX_CYCLES);
+        assert_eq!(timer.progress(), 0.25);
+        timer.update_at(after(start, 75), MAX_CYCLES);
+        assert_eq!(timer.progress(), 0.75);
+        timer.update_at(after(start, 100), MAX_CYCLES);
+        assert_eq!(timer.progress(), 0.);
+
+        // NOTE: Frozen while paused
+        timer.update_at(after(start, 150), MAX_CYCLES);
+        timer.pause();
+        timer.update_at(after(start, 190), MAX_CYCLES);
+        assert_eq!(timer.progress(), 0.5);
+
+        assert_eq!(RepeatingTimer::new(Duration::ZERO).progress(), 0.);
+    }
+
+    #[test]
+    fn clock_going_backwards() {
+        let start = Instant::now();
+        let mut timer = RepeatingTimer::with_last_checked(ms(100), after(start, 1000));
+
+        assert_eq!(timer.checked_update_at(start, MAX_CYCLES), None);
+        assert_eq!(timer.remaining(), ms(100));
+        assert_eq!(timer.update_at(start, MAX_CYCLES), TimerState::Ongoing);
+        assert_eq!(timer.remaining(), ms(100));
+        // NOTE: Only the time since the last update counts, not the gap
+        // the clock went back by
+        assert_eq!(
+            timer.update_at(after(start, 1030), MAX_CYCLES),
+            TimerState::Ongoing
+        );
+        assert_eq!(timer.remaining(), ms(70));
+    }
+}
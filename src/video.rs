@@ -0,0 +1,249 @@
+//! Export of videos by piping raw frames to an external encoder
+//! (`ffmpeg` by default).
+//!
+//! ```ignore
+//! # use lifers_raylib::video::VideoConfig;
+//! frontend.start_video_export(VideoConfig::new("run.mp4", (1920, 1080)).framerate(60))?;
+//! ```
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, SyncSender},
+    thread::{self, JoinHandle},
+};
+
+use crate::recorder;
+
+/// The number of frames that may wait to be written before queueing
+/// another one blocks.
+const QUEUE_LENGTH: usize = 4;
+/// Default frames per second of the video.
+const DEFAULT_FRAMERATE: u32 = 30;
+/// The command run by default, see [`VideoConfig::command()`].
+const DEFAULT_COMMAND: &[&str] = &[
+    "ffmpeg",
+    "-loglevel",
+    "error",
+    "-y",
+    "-f",
+    "rawvideo",
+    "-pix_fmt",
+    "rgba",
+    "-s",
+    "{width}x{height}",
+    "-r",
+    "{framerate}",
+    "-i",
+    "-",
+    "-c:v",
+    "libx264",
+    "-pix_fmt",
+    "yuv420p",
+    "{output}",
+];
+
+/// Settings of a video export (see e.g.
+/// [`generic::RaylibFrontend::start_video_export()`](crate::generic::RaylibFrontend::start_video_export())).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct VideoConfig {
+    /// The file to write.
+    pub output: PathBuf,
+    /// Size of the frames in pixels.
+    ///
+    /// Most codecs (including the default one) need both sides to be
+    /// even.
+    pub size: (u32, u32),
+    /// Frames per second of the video.
+    pub framerate: u32,
+    /// Write a frame every this many generations.
+    pub every: u64,
+    /// The command to run, if not the default one.
+    pub command: Option<Vec<String>>,
+}
+
+impl VideoConfig {
+    /// Creates the settings for a video with the given output file and
+    /// frame size, at 30 frames per second with a frame per generation.
+    pub fn new(output: impl Into<PathBuf>, size: (u32, u32)) -> Self {
+        Self {
+            output: output.into(),
+            size,
+            framerate: DEFAULT_FRAMERATE,
+            every: 1,
+            command: None,
+        }
+    }
+
+    /// Sets the frames per second of the video.
+    #[must_use]
+    pub fn framerate(self, framerate: u32) -> Self {
+        Self { framerate, ..self }
+    }
+
+    /// Writes a frame every `every` generations.
+    #[must_use]
+    pub fn every(self, every: u64) -> Self {
+        Self { every, ..self }
+    }
+
+    /// Sets the command to run (the program followed by its arguments).
+    ///
+    /// It receives the frames as raw RGBA pixels on its standard input.
+    /// `{width}`, `{height}`, `{framerate}` and `{output}` in the
+    /// arguments are replaced with the settings.  By default `ffmpeg`
+    /// is run to encode an H.264 video.
+    #[must_use]
+    pub fn command<A: Into<String>>(self, command: impl IntoIterator<Item = A>) -> Self {
+        Self {
+            command: Some(command.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Returns the command to run, with the placeholders replaced.
+    fn command_line(&self) -> Vec<String> {
+        let output = self.output.to_string_lossy();
+        let replace = |arg: &str| {
+            arg.replace("{width}", &self.size.0.to_string())
+                .replace("{height}", &self.size.1.to_string())
+                .replace("{framerate}", &self.framerate.to_string())
+                .replace("{output}", &output)
+        };
+
+        self.command.as_ref().map_or_else(
+            || DEFAULT_COMMAND.iter().map(|arg| replace(arg)).collect(),
+            |command| command.iter().map(|arg| replace(arg)).collect(),
+        )
+    }
+}
+
+/// A running encoder, fed with frames from a background thread.
+pub(crate) struct VideoExport {
+    child: Child,
+    size: (u32, u32),
+    every: u64,
+    /// The generation of the last frame queued.
+    last: Option<u64>,
+    sender: Option<SyncSender<Vec<u8>>>,
+    errors: Receiver<String>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl VideoExport {
+    /// Starts the encoder and the thread writing to it.
+    pub fn start(config: &VideoConfig) -> io::Result<Self> {
+        let command = config.command_line();
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty video command"))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()?;
+        let Some(mut stdin) = child.stdin.take() else {
+            let _ = child.kill();
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "couldn't open the encoder's input",
+            ));
+        };
+
+        let (sender, frames) = mpsc::sync_channel::<Vec<u8>>(QUEUE_LENGTH);
+        let (error_sender, errors) = mpsc::channel();
+        let worker = thread::Builder::new()
+            .name("video-export".to_owned())
+            .spawn(move || {
+                for frame in frames {
+                    if let Err(error) = stdin.write_all(&frame) {
+                        let _ = error_sender.send(format!("the encoder stopped: {error}"));
+                        return;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            child,
+            size: config.size,
+            every: config.every.max(1),
+            last: None,
+            sender: Some(sender),
+            errors,
+            worker: Some(worker),
+        })
+    }
+
+    /// Returns the size of the frames in pixels.
+    pub const fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Checks whether a frame should be written at the given
+    /// generation.
+    pub fn is_due(&self, generation: u64) -> bool {
+        recorder::is_due(self.last, self.every, generation)
+    }
+
+    /// Queues the RGBA pixels of the frame rendered at the given
+    /// generation, blocking if too many are waiting to be written
+    /// already.
+    ///
+    /// Fails if the encoder stopped accepting frames.
+    pub fn push(&mut self, generation: u64, pixels: Vec<u8>) -> Result<(), String> {
+        self.error()?;
+        self.last = Some(generation);
+
+        let sent = self
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.send(pixels).is_ok());
+
+        if sent {
+            Ok(())
+        } else {
+            Err(self
+                .errors
+                .recv()
+                .unwrap_or_else(|_| "the writing thread stopped".to_owned()))
+        }
+    }
+
+    /// Fails if the encoder stopped accepting frames.
+    pub fn error(&self) -> Result<(), String> {
+        self.errors.try_recv().map_or(Ok(()), Err)
+    }
+
+    /// Writes the queued frames and waits for the encoder to finish.
+    ///
+    /// Fails if it stopped accepting frames or exited unsuccessfully.
+    pub fn finish(mut self) -> Result<(), String> {
+        self.join();
+        self.error()?;
+
+        let status = self.child.wait().map_err(|e| e.to_string())?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("the encoder exited with {status}"))
+        }
+    }
+
+    /// Closes the queue and waits for the writing thread to empty it,
+    /// which closes the encoder's input.
+    fn join(&mut self) {
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for VideoExport {
+    fn drop(&mut self) {
+        self.join();
+        let _ = self.child.wait();
+    }
+}
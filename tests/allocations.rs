@@ -0,0 +1,79 @@
+//! Checks that drawing the grid doesn't allocate once the frontend is
+//! warmed up, with the allocations counted by the `count-allocations`
+//! feature:
+//!
+//! ```sh
+//! cargo test --test allocations --features count-allocations
+//! ```
+//!
+//! It fails on the first frame that allocated.  It runs without a test
+//! harness, as the window has to be created on the main thread, and it
+//! needs a display (or a virtual one, e.g. Xvfb) even though the window
+//! is hidden.  Run it without the `rayon` feature, rendering the cells
+//! in parallel allocates.
+
+use std::time::Duration;
+
+use lifers::prelude::*;
+use lifers_raylib::{alloc_count::CountingAllocator, life_like::FrontendBuilder};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use raylib::prelude::*;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const WINDOW_SIZE: (u32, u32) = (800, 800);
+const CELLS_N: (usize, usize) = (200, 200);
+const UPDATE_RATE: Duration = Duration::from_millis(10);
+/// The frames in which the buffers are allowed to grow.
+const WARM_UP_FRAMES: usize = 60;
+const FRAMES: usize = 600;
+
+struct Cell;
+
+impl RenderCell<Color> for Cell {
+    fn render_cell(&self) -> Color {
+        Color::WHITE
+    }
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let game = life_like::AutomatonBuilder::new(1)
+        .init(|| {
+            (0..10_000)
+                .map(|_| {
+                    let pos = (rng.gen_range(0..CELLS_N.0), rng.gen_range(0..CELLS_N.1));
+
+                    (pos, Cell)
+                })
+                .collect()
+        })
+        .map(|pos, _, cells| life_like::count_neighbors(pos, 1, cells))
+        .run(|_, cell, neighbors_n| match cell {
+            Some(_) => (2..=3).contains(&neighbors_n).then_some(Cell),
+            None => (neighbors_n == 3).then_some(Cell),
+        });
+
+    let mut frontend = FrontendBuilder::default()
+        .window_size(WINDOW_SIZE)
+        .grid_size(CELLS_N)
+        .update_rate(UPDATE_RATE)
+        .headless(true)
+        .finish(game);
+    let mut frames = 0;
+
+    while frames < FRAMES && frontend.run_frame::<Color>() {
+        let allocations = frontend.frame_stats().grid_allocations;
+
+        assert!(
+            frames < WARM_UP_FRAMES || allocations == 0,
+            "frame {frames} allocated {allocations} times while drawing the grid"
+        );
+
+        frames += 1;
+    }
+
+    assert_eq!(frames, FRAMES, "the frontend stopped after {frames} frames");
+    println!("allocations: no allocations in {FRAMES} frames after warming up");
+}
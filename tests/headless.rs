@@ -0,0 +1,358 @@
+//! Steps headless frontends and checks what they draw:
+//!
+//! ```sh
+//! cargo test --test headless
+//! ```
+//!
+//! It runs without a test harness, as the window has to be created on
+//! the main thread, and it needs a display (or a virtual one, e.g.
+//! Xvfb) even though the windows are hidden.
+
+use std::{cell::Cell as Counter, rc::Rc, thread, time::Duration};
+
+use lifers::prelude::*;
+use lifers_raylib::{
+    clipboard::MemoryClipboard,
+    life_like::{FrontendBuilder, RaylibFrontend},
+};
+use raylib::prelude::*;
+
+const WINDOW_SIZE: (u32, u32) = (50, 50);
+const CELLS_N: (usize, usize) = (5, 5);
+/// The side of the cells, in pixels.
+const CELL_SIZE: usize = 10;
+/// A blinker, alternating between a row and a column.
+const BLINKER: [(usize, usize); 3] = [(1, 2), (2, 2), (3, 2)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Cell(u8);
+
+impl RenderCell<Color> for Cell {
+    fn render_cell(&self) -> Color {
+        Color::WHITE
+    }
+}
+
+/// Makes a paused headless frontend running Conway's Game of Life with
+/// the given cells alive.
+fn frontend(alive: &[(usize, usize)]) -> RaylibFrontend<Cell, impl Sized> {
+    let alive = alive.to_vec();
+    let game = life_like::AutomatonBuilder::new(1)
+        .init(move || alive.into_iter().map(|pos| (pos, Cell(0))).collect())
+        .map(|pos, _, cells| life_like::count_neighbors(pos, 1, cells))
+        .run(|_, cell, neighbors_n| match cell {
+            Some(_) => (2..=3).contains(&neighbors_n).then_some(Cell(0)),
+            None => (neighbors_n == 3).then_some(Cell(0)),
+        });
+
+    FrontendBuilder::default()
+        .window_size(WINDOW_SIZE)
+        .grid_size(CELLS_N)
+        .cell_margin(0)
+        .default_color(Color::BLACK)
+        .steps_per_second(0.)
+        .cache_grid(true)
+        .headless(true)
+        .finish(game)
+}
+
+/// Returns the cells drawn as alive by the frontend, going by the
+/// colors at their centers.
+fn drawn_cells<D>(frontend: &mut RaylibFrontend<Cell, D>) -> Vec<(usize, usize)> {
+    let image = frontend
+        .render_to_image::<Color>(WINDOW_SIZE, false)
+        .unwrap();
+    let pixels = image.get_image_data();
+    let width = usize::try_from(image.width()).unwrap();
+
+    (0..CELLS_N.1)
+        .flat_map(|y| (0..CELLS_N.0).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let center = |cell: usize| cell * CELL_SIZE + CELL_SIZE / 2;
+
+            pixels[center(y) * width + center(x)] == Color::WHITE
+        })
+        .collect()
+}
+
+fn stepping() {
+    let mut frontend = frontend(&BLINKER);
+
+    assert!(frontend.is_headless());
+    assert_eq!(drawn_cells(&mut frontend), BLINKER);
+
+    frontend.step();
+    assert_eq!(frontend.generation(), 1);
+    assert_eq!(drawn_cells(&mut frontend), [(2, 1), (2, 2), (2, 3)]);
+
+    // NOTE: Paused, so the frames only draw the grid
+    assert!(frontend.run_frame::<Color>());
+    assert_eq!(frontend.generation(), 1);
+
+    frontend.step();
+    assert_eq!(drawn_cells(&mut frontend), BLINKER);
+
+    // NOTE: Only closed on request while headless
+    assert!(!frontend.window_should_close());
+    frontend.request_close();
+    assert!(frontend.window_should_close());
+    assert!(!frontend.run_frame::<Color>());
+}
+
+fn reopen() {
+    let mut first = frontend(&BLINKER);
+
+    // NOTE: Fills the textures of the grid cache
+    assert!(first.run_frame::<Color>());
+    assert!(first.run_frame::<Color>());
+    assert_eq!(first.into_automaton().cells().len(), BLINKER.len());
+
+    let mut second = frontend(&BLINKER);
+
+    assert!(second.run_frame::<Color>());
+    assert_eq!(drawn_cells(&mut second), BLINKER);
+}
+
+fn reset() {
+    let mut frontend = frontend(&BLINKER);
+
+    frontend.step();
+    frontend.step();
+    frontend.step();
+    assert_eq!(frontend.generation(), 3);
+
+    frontend.reset();
+    assert_eq!(frontend.generation(), 0);
+    assert_eq!(drawn_cells(&mut frontend), [(2, 1), (2, 2), (2, 3)]);
+
+    frontend.step();
+    assert_eq!(frontend.generation(), 1);
+    assert_eq!(drawn_cells(&mut frontend), BLINKER);
+}
+
+fn resize() {
+    let mut frontend = frontend(&BLINKER);
+
+    assert_eq!(frontend.overlay_context().cell_size, 10.);
+
+    // NOTE: Noticed by rendering, without handling the window events
+    // explicitly
+    frontend.raylib_handle().set_window_size(100, 100);
+    frontend
+        .render_to_image::<Color>((100, 100), false)
+        .unwrap();
+    assert_eq!(frontend.overlay_context().cell_size, 20.);
+
+    frontend.raylib_handle().set_window_size(50, 50);
+    assert!(frontend.run_frame::<Color>());
+    assert_eq!(frontend.overlay_context().cell_size, 10.);
+    assert_eq!(drawn_cells(&mut frontend), BLINKER);
+}
+
+fn dpi_scale() {
+    let mut frontend = frontend(&BLINKER);
+
+    frontend.set_dpi_scale(Some(2.));
+    assert_eq!(frontend.dpi_scale(), 2.);
+
+    // NOTE: Laid out in logical units, like the window, with only the
+    // grid cache drawn at twice the size
+    assert!(frontend.run_frame::<Color>());
+    assert!(frontend.run_frame::<Color>());
+
+    let context = frontend.overlay_context();
+
+    assert_eq!(context.cell_size, 10.);
+    assert_eq!(
+        (context.grid_rect.width, context.grid_rect.height),
+        (50., 50.)
+    );
+    assert_eq!(
+        frontend.screen_to_cell(Vector2::new(25., 15.)),
+        Some((2, 1))
+    );
+    assert_eq!(drawn_cells(&mut frontend), BLINKER);
+}
+
+fn cell_accessors() {
+    let mut frontend = frontend(&[]);
+    let cell = |frontend: &RaylibFrontend<Cell, _>, pos: (usize, usize)| {
+        frontend.automaton().cells().get(&pos).copied()
+    };
+
+    assert_eq!(frontend.insert_cell((1, 1), Cell(1)), None);
+    assert_eq!(cell(&frontend, (1, 1)), Some(Cell(1)));
+    assert_eq!(frontend.insert_cell((1, 1), Cell(2)), Some(Cell(1)));
+    assert_eq!(cell(&frontend, (1, 1)), Some(Cell(2)));
+    assert_eq!(drawn_cells(&mut frontend), [(1, 1)]);
+
+    assert_eq!(frontend.remove_cell((1, 1)), Some(Cell(2)));
+    assert_eq!(cell(&frontend, (1, 1)), None);
+    assert_eq!(frontend.remove_cell((1, 1)), None);
+    assert!(drawn_cells(&mut frontend).is_empty());
+
+    // NOTE: Outside of the displayed grid
+    assert_eq!(frontend.insert_cell((100, 7), Cell(3)), None);
+    assert_eq!(cell(&frontend, (100, 7)), Some(Cell(3)));
+    assert!(drawn_cells(&mut frontend).is_empty());
+    assert_eq!(frontend.remove_cell((100, 7)), Some(Cell(3)));
+
+    assert!(frontend.toggle_cell((3, 0), || Cell(4)));
+    assert_eq!(cell(&frontend, (3, 0)), Some(Cell(4)));
+    assert_eq!(drawn_cells(&mut frontend), [(3, 0)]);
+    assert!(!frontend.toggle_cell((3, 0), || Cell(5)));
+    assert_eq!(cell(&frontend, (3, 0)), None);
+    assert!(frontend.automaton().cells().is_empty());
+}
+
+fn live_bounds() {
+    let mut frontend = frontend(&[]);
+
+    assert_eq!(frontend.live_bounds(), None);
+
+    frontend.insert_cell((3, 1), Cell(0));
+    assert_eq!(frontend.live_bounds(), Some(((3, 1), (3, 1))));
+    frontend.remove_cell((3, 1));
+    assert_eq!(frontend.live_bounds(), None);
+    // NOTE: Only one window can be open at a time
+    drop(frontend);
+
+    let full: Vec<_> = (0..CELLS_N.1)
+        .flat_map(|y| (0..CELLS_N.0).map(move |x| (x, y)))
+        .collect();
+    let mut full = self::frontend(&full);
+
+    assert_eq!(full.live_bounds(), Some(((0, 0), (4, 4))));
+    full.insert_cell((7, 9), Cell(0));
+    assert_eq!(full.live_bounds(), Some(((0, 0), (7, 9))));
+    drop(full);
+
+    // NOTE: Recomputed after every step
+    let mut blinker = self::frontend(&BLINKER);
+
+    assert_eq!(blinker.live_bounds(), Some(((1, 2), (3, 2))));
+    blinker.step();
+    assert_eq!(blinker.live_bounds(), Some(((2, 1), (2, 3))));
+}
+
+fn on_finish() {
+    let mut frontend = frontend(&BLINKER);
+    let calls = Rc::new(Counter::new(0));
+    let counter = Rc::clone(&calls);
+
+    frontend.set_max_generations(Some(3));
+    frontend.set_on_finish(Some(Box::new(move |_| counter.set(counter.get() + 1))));
+    frontend.set_steps_per_second(1000.);
+    frontend.resume();
+
+    // NOTE: Ticking goes on past the generation limit
+    for _ in 0..100 {
+        frontend.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(frontend.generation(), 3);
+    assert_eq!(calls.get(), 1);
+
+    frontend.step();
+    frontend.tick();
+    assert_eq!(calls.get(), 1);
+}
+
+fn step_n_limit() {
+    let mut frontend = frontend(&BLINKER);
+    let calls = Rc::new(Counter::new(0));
+    let counter = Rc::clone(&calls);
+
+    frontend.set_max_generations(Some(3));
+    frontend.set_on_finish(Some(Box::new(move |_| counter.set(counter.get() + 1))));
+    frontend.resume();
+
+    assert!(frontend.step_n(10).is_some());
+    assert_eq!(frontend.generation(), 3);
+    assert_eq!(calls.get(), 1);
+    assert!(frontend.is_paused());
+    assert!(frontend.step_n(10).is_none());
+    assert_eq!(frontend.generation(), 3);
+}
+
+fn copy_selection() {
+    let mut frontend = frontend(&BLINKER);
+
+    frontend.set_clipboard(MemoryClipboard::new());
+
+    assert!(!frontend.copy_selection_to_clipboard());
+    assert!(frontend
+        .toasts()
+        .any(|text| text.starts_with("Nothing selected")));
+
+    // NOTE: No cells in the selection
+    frontend.select((0, 0), (4, 1));
+    assert!(!frontend.copy_selection_to_clipboard());
+    assert!(frontend.toasts().any(|text| text == "Nothing to copy"));
+    assert_eq!(frontend.clipboard_text(), None);
+
+    frontend.select((3, 4), (2, 0));
+    assert_eq!(frontend.selection(), Some(((2, 0), (3, 4))));
+    assert!(frontend.copy_selection_to_clipboard());
+    assert!(frontend.toasts().any(|text| text == "Copied 2 cells"));
+
+    let text = frontend.clipboard_text().unwrap();
+
+    assert_eq!(
+        text.lines().collect::<Vec<_>>(),
+        ["#C generated by lifers-raylib", "x = 2, y = 1", "2o!"]
+    );
+
+    frontend.clear_selection();
+    assert_eq!(frontend.selection(), None);
+    assert!(!frontend.copy_selection_to_clipboard());
+    assert_eq!(frontend.clipboard_text(), Some(text));
+}
+
+#[cfg(feature = "serde")]
+fn newer_session() {
+    use lifers_raylib::session::{SessionError, FORMAT_VERSION};
+
+    let path = std::env::temp_dir().join("lifers-raylib-headless-session.json");
+    let mut frontend = frontend(&BLINKER);
+
+    frontend.save_state(&path).unwrap();
+    frontend.step();
+
+    let text = std::fs::read_to_string(&path).unwrap().replacen(
+        &format!("\"version\":{FORMAT_VERSION}"),
+        &format!("\"version\":{}", FORMAT_VERSION + 1),
+        1,
+    );
+
+    std::fs::write(&path, text).unwrap();
+
+    let loaded = frontend.load_state(&path);
+    let _ = std::fs::remove_file(&path);
+
+    assert!(matches!(
+        loaded,
+        Err(SessionError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+    ));
+    // NOTE: Left as it was
+    assert_eq!(frontend.generation(), 1);
+}
+
+fn main() {
+    stepping();
+    reopen();
+    reset();
+    resize();
+    dpi_scale();
+    cell_accessors();
+    live_bounds();
+    on_finish();
+    step_n_limit();
+    copy_selection();
+    #[cfg(feature = "serde")]
+    newer_session();
+
+    println!("headless: ok");
+}